@@ -0,0 +1,108 @@
+//! The value a solver returns. The harness renders it the same way for every
+//! year: integers in decimal, lists joined by commas, one line per answer.
+
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    /// Wide enough for any signed or unsigned 64-bit answer.
+    Int(i128),
+    Str(String),
+    List(Vec<Answer>),
+}
+
+impl Answer {
+    /// Reads back an answer as it is stored in an `.out` file: an integer, a
+    /// comma-separated list, or failing that the text itself.
+    pub fn parse(text: &str) -> Answer {
+        let text = text.trim();
+        if let Ok(i) = text.parse() {
+            Answer::Int(i)
+        } else if text.contains(',') && !text.contains('\n') {
+            Answer::List(text.split(',').map(Answer::parse).collect())
+        } else {
+            Answer::Str(text.to_string())
+        }
+    }
+
+    /// Whether `expected`, as stored, is this answer. Both sides go through
+    /// `parse`, so `1,7` is `[1, 7]` and a string that happens to hold a
+    /// number is that number.
+    pub fn matches(&self, expected: &str) -> bool {
+        Answer::parse(expected) == Answer::parse(&self.to_string())
+    }
+
+    /// The contents of an `.out` file holding this answer.
+    pub fn stored(&self) -> String {
+        format!("{}\n", self)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(i) => write!(f, "{}", i),
+            Answer::Str(s) => write!(f, "{}", s.trim_end()),
+            Answer::List(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(i: $t) -> Answer {
+                Answer::Int(i as i128)
+            }
+        })*
+    };
+}
+
+from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<i128> for Answer {
+    fn from(i: i128) -> Answer {
+        Answer::Int(i)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Str(s.to_string())
+    }
+}
+
+impl<T: Into<Answer>> From<Vec<T>> for Answer {
+    fn from(items: Vec<T>) -> Answer {
+        Answer::List(items.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::*;
+
+    #[test]
+    fn stored_answers_read_back() {
+        assert_eq!(Answer::from(vec![1u8, 7, 6]).stored(), "1,7,6\n");
+        assert!(Answer::from(42u64).matches("42\n"));
+        assert!(Answer::from("42").matches("42"));
+        assert!(Answer::from(vec!["fhc", "z06"]).matches("fhc, z06\n"));
+        assert!(!Answer::from(vec![1, 2]).matches("1,2,3"));
+        assert!(!Answer::from(-1i64).matches("1"));
+    }
+}
@@ -0,0 +1,88 @@
+//! Diagnostics a solver wants to keep next to its answer, such as a drawn
+//! grid or a graph to feed to Graphviz. Each run gets its own directory,
+//! `target/aoc-artifacts/dayNN/<input>-partN/`, emptied when the run starts.
+//!
+//! Solvers don't need to know which run they are in: the harness sets that up
+//! around every solve, and outside of one (e.g. in unit tests) artifacts are
+//! dropped.
+
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+thread_local! {
+    static CURRENT: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+}
+
+fn root() -> PathBuf {
+    match std::env::var_os("CARGO_TARGET_DIR") {
+        Some(target) => PathBuf::from(target),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap_or(Path::new(".")).join("target"),
+    }.join("aoc-artifacts")
+}
+
+/// Where the artifacts of `input`, solved for `day`/`part`, end up.
+pub fn dir(day: u32, part: u32, input: &str) -> PathBuf {
+    let stem = input.strip_suffix(".in").unwrap_or(input);
+    root().join(format!("day{:02}", day)).join(format!("{}-part{}", stem, part))
+}
+
+/// Sends the artifacts written on this thread to `dir` until the guard is dropped.
+pub(crate) struct Scope(());
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.borrow_mut().take());
+    }
+}
+
+pub(crate) fn enter(dir: PathBuf) -> Scope {
+    let _ = fs::remove_dir_all(&dir);
+    CURRENT.with(|current| *current.borrow_mut() = Some(dir));
+    Scope(())
+}
+
+/// Writing an artifact never fails the run, a problem is only reported.
+fn with_path(name: &str, write: impl FnOnce(&Path) -> std::io::Result<()>) {
+    CURRENT.with(|current| {
+        let Some(dir) = current.borrow().clone() else { return };
+        let path = dir.join(name);
+        if let Err(e) = fs::create_dir_all(&dir).and_then(|_| write(&path)) {
+            eprintln!("warning: can't write {}: {}", path.display(), e);
+        }
+    })
+}
+
+/// Stores `contents` as the artifact `name`, replacing an earlier one.
+pub fn save(name: &str, contents: impl AsRef<[u8]>) {
+    with_path(name, |path| fs::write(path, contents))
+}
+
+/// Appends `line` to the artifact `name`, for traces built up while solving.
+pub fn trace(name: &str, line: impl Display) {
+    with_path(name, |path| writeln!(OpenOptions::new().create(true).append(true).open(path)?, "{}", line))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::artifacts::*;
+
+    #[test]
+    fn artifacts_go_to_the_current_run() {
+        let dir = std::env::temp_dir().join(format!("aoc-artifacts-test-{}", std::process::id()));
+        save("dropped.txt", "outside of a run");
+        {
+            let _scope = enter(dir.clone());
+            save("grid.txt", "#.\n.#\n");
+            trace("log.txt", 1);
+            trace("log.txt", 2);
+        }
+        save("dropped.txt", "outside of a run");
+        assert_eq!(fs::read_to_string(dir.join("grid.txt")).unwrap(), "#.\n.#\n");
+        assert_eq!(fs::read_to_string(dir.join("log.txt")).unwrap(), "1\n2\n");
+        assert!(!dir.join("dropped.txt").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
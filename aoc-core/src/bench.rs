@@ -0,0 +1,152 @@
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+use crate::isolate::{self, Outcome};
+use crate::problem::Timings;
+use crate::registry::{Run, Solver};
+
+/// Median parse and solve times of one run, in nanoseconds.
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct Measurement {
+    pub(crate) day: u32,
+    pub(crate) part: u32,
+    pub(crate) input: String,
+    pub(crate) repeat: u32,
+    pub(crate) parse_ns: u64,
+    pub(crate) solve_ns: u64,
+}
+
+impl Measurement {
+    pub(crate) fn new(solver: &Solver, run: &Run, repeat: u32, timings: Timings) -> Self {
+        Measurement {
+            day: solver.day,
+            part: solver.part,
+            input: run.input.clone(),
+            repeat,
+            parse_ns: timings.parse.as_nanos() as u64,
+            solve_ns: timings.solve.as_nanos() as u64,
+        }
+    }
+
+    pub(crate) fn total_ns(&self) -> u64 {
+        self.parse_ns + self.solve_ns
+    }
+
+    pub(crate) fn name(&self) -> String {
+        name(self.day, self.part, &self.input)
+    }
+}
+
+/// A run that gave no answer to time, with the outcome it got instead.
+#[derive(Serialize)]
+pub(crate) struct Failure {
+    pub(crate) day: u32,
+    pub(crate) part: u32,
+    pub(crate) input: String,
+    pub(crate) outcome: &'static str,
+    pub(crate) message: String,
+}
+
+impl Failure {
+    fn new(solver: &Solver, run: &Run, outcome: &Outcome) -> Self {
+        Failure {
+            day: solver.day,
+            part: solver.part,
+            input: run.input.clone(),
+            outcome: outcome.label(),
+            message: outcome.to_string(),
+        }
+    }
+
+    pub(crate) fn name(&self) -> String {
+        name(self.day, self.part, &self.input)
+    }
+}
+
+fn name(day: u32, part: u32, input: &str) -> String {
+    format!("problem{:02}/{} (part {})", day, input, part)
+}
+
+#[derive(Serialize)]
+pub(crate) struct Report {
+    pub(crate) runs: Vec<Measurement>,
+    pub(crate) failures: Vec<Failure>,
+}
+
+/// Runs a group of runs sharing an input (see `registry::groups`) `repeat`
+/// times and returns each answer along with the median timings. The parse is
+/// timed once for the group and counted with its first run. Every parse and
+/// solve goes through `isolate` like any other run, so a run that errors,
+/// panics or takes longer than `timeout` fails on its own.
+pub(crate) fn measure(group: &[(&Solver, &Run)], repeat: u32, timeout: Option<Duration>) -> Vec<Result<(Answer, Measurement), Failure>> {
+    let (first, first_run) = group[0];
+    let mut parsed = None;
+    let mut parse = Vec::new();
+    for _ in 0..repeat {
+        match isolate::isolated(first.parse_task(first_run), timeout) {
+            Ok((latest, time)) => {
+                parsed = Some(latest);
+                parse.push(time);
+            }
+            Err(failure) => return group.iter().map(|&(solver, run)| Err(Failure::new(solver, run, &failure))).collect(),
+        }
+    }
+    let (parsed, parse) = (parsed.unwrap(), median(&mut parse));
+    group.iter().enumerate().map(|(i, &(solver, run))| {
+        let mut answer = Answer::Str(String::new());
+        let mut solve = Vec::new();
+        for _ in 0..repeat {
+            let (latest, time) = isolate::isolated(solver.solve_task(run, parsed.clone()), timeout)
+                .map_err(|failure| Failure::new(solver, run, &failure))?;
+            answer = latest;
+            solve.push(time);
+        }
+        let parse = if i == 0 { parse } else { Duration::ZERO };
+        let timings = Timings { parse, solve: median(&mut solve) };
+        Ok((answer, Measurement::new(solver, run, repeat, timings)))
+    }).collect()
+}
+
+fn median(samples: &mut [Duration]) -> Duration {
+    samples.sort();
+    samples[samples.len() / 2]
+}
+
+pub(crate) fn format_ns(ns: u64) -> String {
+    match ns {
+        0..1_000_000 => format!("{:.1}µs", ns as f64 / 1e3),
+        1_000_000..1_000_000_000 => format!("{:.2}ms", ns as f64 / 1e6),
+        _ => format!("{:.2}s", ns as f64 / 1e9),
+    }
+}
+
+impl Report {
+    pub(crate) fn table(&self) -> String {
+        let names = self.runs.iter().map(Measurement::name).chain(self.failures.iter().map(Failure::name));
+        let width = names.map(|name| name.len()).max().unwrap_or(0);
+        let mut lines = vec![format!("{:<width$}  {:>10}  {:>10}  {:>10}", "run", "parse", "solve", "total")];
+        for m in &self.runs {
+            // Runs after the first on an input reuse its parse.
+            let parse = if m.parse_ns == 0 { "-".to_string() } else { format_ns(m.parse_ns) };
+            lines.push(format!("{:<width$}  {:>10}  {:>10}  {:>10}",
+                m.name(), parse, format_ns(m.solve_ns), format_ns(m.total_ns())));
+        }
+        for f in &self.failures {
+            lines.push(format!("{:<width$}  {}: {}", f.name(), f.outcome, f.message));
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bench::*;
+
+    #[test]
+    fn durations_pick_a_readable_unit() {
+        assert_eq!(format_ns(1_500), "1.5µs");
+        assert_eq!(format_ns(12_345_678), "12.35ms");
+        assert_eq!(format_ns(4_200_000_000), "4.20s");
+    }
+}
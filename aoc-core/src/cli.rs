@@ -0,0 +1,558 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use anyhow::{bail, Context};
+use clap::{ArgAction, Args, CommandFactory, FromArgMatches, Parser, Subcommand};
+
+use crate::artifacts;
+use crate::bench::{self, Measurement, Report};
+use crate::discover;
+use crate::history::{self, History};
+use crate::isolate::{self, Outcome};
+use crate::log::{self, Level};
+use crate::normalize;
+use crate::parallel;
+use crate::registry::{self, Profile, Registry, Run, Solver};
+use crate::scaffold::{self, Template};
+use crate::verify;
+use crate::watch;
+
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    verbosity: Verbosity,
+    /// Directory with the problemNN data directories
+    #[arg(long, global = true, value_name = "DIR", env = "AOC_DATA", default_value = "data")]
+    data: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct Verbosity {
+    /// Show the solvers' debug output, twice to also show trace output
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+    /// Show trace output of one day, e.g. `day24`; can be repeated
+    #[arg(long, global = true, value_name = "DAY", value_parser = parse_day)]
+    trace: Vec<u32>,
+}
+
+fn parse_day(s: &str) -> Result<u32, String> {
+    let digits = s.strip_prefix("problem").or_else(|| s.strip_prefix("day")).unwrap_or(s);
+    digits.parse().map_err(|_| format!("expected a day like `day24` or `24`, not `{}`", s))
+}
+
+impl Verbosity {
+    fn level(&self) -> Option<Level> {
+        match self.verbose {
+            0 => None,
+            1 => Some(Level::Debug),
+            _ => Some(Level::Trace),
+        }
+    }
+
+    /// The same flags, for commands that start another run of the binary.
+    fn args(&self) -> Vec<String> {
+        let verbose = (self.verbose > 0).then(|| format!("-{}", "v".repeat(self.verbose as usize)));
+        verbose.into_iter().chain(self.trace.iter().map(|day| format!("--trace=day{:02}", day))).collect()
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run solvers selected by day, part and input name and print their answers
+    Run(RunCommand),
+    /// Compare answers with the stored expected .out files
+    Verify(RunArgs),
+    /// Overwrite the stored expected .out files with the current answers
+    Bless(RunArgs),
+    /// Time parsing and solving separately, repeating every run
+    Bench(BenchArgs),
+    /// Flag runs whose answer changed or that got slower since they were last recorded
+    Compare(CompareArgs),
+    /// List data files no registered run uses, and runs whose files are missing
+    Orphans,
+    /// List input files that are not in the shape solvers get them in: CRLF line
+    /// endings, trailing whitespace, a missing final newline or blank lines at the end
+    LintInputs,
+    /// Verify a day again every time its module or its data change
+    Watch(WatchArgs),
+    /// Generate the module, registration and data directory of a new day
+    NewDay {
+        day: u32,
+    },
+}
+
+#[derive(Args)]
+struct Selection {
+    /// Run every registered day
+    #[arg(long, conflicts_with = "day")]
+    all: bool,
+    #[arg(long)]
+    day: Option<u32>,
+    #[arg(long, requires = "day")]
+    part: Option<u32>,
+    /// Input file name inside data/problemNN, e.g. `01.in`
+    #[arg(long, requires = "day")]
+    input: Option<String>,
+    /// Skip real puzzle inputs, run only the sample ones
+    #[arg(long)]
+    samples_only: bool,
+    /// Also run inputs marked as slow
+    #[arg(long)]
+    include_slow: bool,
+}
+
+#[derive(Args)]
+struct RunArgs {
+    #[command(flatten)]
+    selection: Selection,
+    /// Give up on a run after this many seconds, 0 waits forever. A run that
+    /// was given up on keeps its core busy until all other runs are done
+    #[arg(long, default_value_t = 60)]
+    timeout: u64,
+    /// How many runs to solve at once; output still comes in registry order
+    #[arg(long, short, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: u32,
+}
+
+/// The `--timeout` of a command, `0` meaning none.
+fn timeout(seconds: u64) -> Option<Duration> {
+    (seconds > 0).then(|| Duration::from_secs(seconds))
+}
+
+impl RunArgs {
+    fn timeout(&self) -> Option<Duration> {
+        timeout(self.timeout)
+    }
+
+    /// Solves every selected run and hands the outcomes to `emit`, runs on one
+    /// input in registry order right after each other, as they share a parse.
+    fn for_each_outcome<E>(&self, registry: &Registry, mut emit: E) -> anyhow::Result<()>
+    where
+        E: FnMut(&Solver, &Run, Outcome) -> anyhow::Result<()>,
+    {
+        let groups = registry::groups(&select(registry, &self.selection)?);
+        let timeout = self.timeout();
+        parallel::for_each_ordered(&groups, self.jobs as usize,
+            |group| isolate::solve_group(group, timeout),
+            |group, outcomes| {
+                for (&(solver, run), outcome) in group.iter().zip(outcomes) {
+                    emit(solver, run, outcome)?;
+                }
+                Ok(())
+            })
+    }
+}
+
+#[derive(Args)]
+struct RunCommand {
+    #[command(flatten)]
+    args: RunArgs,
+    /// Solve this file instead of the day's inputs and print nothing but the
+    /// answers, one line per part; `-` reads standard input
+    #[arg(value_name = "FILE", requires = "day", conflicts_with = "input")]
+    file: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    #[arg(long)]
+    day: u32,
+    /// Give up on a run after this many seconds, 0 waits forever
+    #[arg(long, default_value_t = 60)]
+    timeout: u64,
+    /// How many runs to solve at once
+    #[arg(long, short, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: u32,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[command(flatten)]
+    selection: Selection,
+    /// How many times to repeat each run; the median is reported
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+    repeat: u32,
+    /// Give up on a repetition of a run after this many seconds, 0 waits forever
+    #[arg(long, default_value_t = 60)]
+    timeout: u64,
+    /// Also write the report as JSON to this file
+    #[arg(long)]
+    json: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct CompareArgs {
+    #[command(flatten)]
+    selection: Selection,
+    /// How many times to repeat each run; the median is compared
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+    repeat: u32,
+    /// Give up on a repetition of a run after this many seconds, 0 waits forever
+    #[arg(long, default_value_t = 60)]
+    timeout: u64,
+    /// Flag runs that take more than this many times as long as before
+    #[arg(long, default_value_t = 1.3)]
+    threshold: f64,
+}
+
+impl Selection {
+    fn matches(&self, solver: &Solver, run: &Run) -> bool {
+        if self.day.is_some_and(|day| day != solver.day)
+            || self.part.is_some_and(|part| part != solver.part)
+            || self.input.as_ref().is_some_and(|input| *input != run.input) {
+            return false;
+        }
+        if self.samples_only && !run.is_sample() {
+            return false;
+        }
+        !run.slow || self.include_slow || self.input.is_some()
+    }
+}
+
+fn select<'a>(registry: &'a Registry, selection: &Selection) -> anyhow::Result<Vec<(&'a Solver, &'a Run)>> {
+    if !selection.all && selection.day.is_none() {
+        bail!("nothing to run: pass --day N or --all");
+    }
+    let root = registry::data_root();
+    if !root.is_dir() {
+        bail!("no data directory at {}: run from the year's crate directory or pass --data", root.display());
+    }
+    let selected: Vec<_> = registry.solvers.iter().flat_map(|solver| {
+        solver.runs.iter().map(move |run| (solver, run))
+    }).filter(|(solver, run)| selection.matches(solver, run)).collect();
+    if selected.is_empty() {
+        bail!("no registered solver matches the selection");
+    }
+    Ok(selected)
+}
+
+/// How many runs ended up with each label, printed once everything ran.
+#[derive(Default)]
+struct Summary {
+    counts: Vec<(&'static str, usize)>,
+}
+
+impl Summary {
+    fn add(&mut self, label: &'static str) {
+        match self.counts.iter_mut().find(|(l, _)| *l == label) {
+            Some((_, count)) => *count += 1,
+            None => self.counts.push((label, 1)),
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.counts.iter().map(|(_, count)| count).sum()
+    }
+
+    fn failed(&self) -> usize {
+        self.counts.iter().filter(|(label, _)| *label != "ok").map(|(_, count)| count).sum()
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts: Vec<_> = self.counts.iter().map(|(label, count)| format!("{} {}", count, label)).collect();
+        write!(f, "summary: {}", counts.join(", "))
+    }
+}
+
+fn run(registry: &Registry, args: RunArgs) -> anyhow::Result<()> {
+    let mut history = History::start();
+    let mut summary = Summary::default();
+    args.for_each_outcome(registry, |solver, run, outcome| {
+        println!("Solving {}/{} (part {}):", solver.dir(), run.input, solver.part);
+        match &outcome {
+            Outcome::Done(answer, timings) => {
+                println!("{}", outcome);
+                history.add(answer, Measurement::new(solver, run, 1, *timings));
+                let artifacts = artifacts::dir(solver.day, solver.part, &run.input);
+                if artifacts.exists() {
+                    println!("(artifacts in {})", artifacts.display());
+                }
+            }
+            _ => println!("{}: {}", outcome.label(), outcome),
+        }
+        summary.add(outcome.label());
+        Ok(())
+    })?;
+    history.save()?;
+    println!("{}", summary);
+    if summary.failed() > 0 {
+        bail!("{} of {} runs failed", summary.failed(), summary.total());
+    }
+    Ok(())
+}
+
+/// `run FILE`: solves an input that isn't in the data directory with each
+/// selected part of the day. It's solved like a puzzle input, or like a sample
+/// with `--samples-only`, with the parameters of the day's first such run.
+fn run_file(registry: &Registry, selection: &Selection, file: &Path) -> anyhow::Result<()> {
+    let (text, source, name) = if file == Path::new("-") {
+        (io::read_to_string(io::stdin().lock()).context("can't read standard input")?, PathBuf::from("<stdin>"), "stdin".to_string())
+    } else {
+        let text = fs::read_to_string(file).with_context(|| format!("can't read {}", file.display()))?;
+        let name = file.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        (text, file.to_path_buf(), name)
+    };
+    let profile = if selection.samples_only { Profile::Sample } else { Profile::Full };
+    let solvers: Vec<_> = registry.solvers.iter()
+        .filter(|solver| selection.day == Some(solver.day) && selection.part.is_none_or(|part| part == solver.part))
+        .collect();
+    if solvers.is_empty() {
+        bail!("no registered solver matches the selection");
+    }
+    for solver in solvers {
+        let run = solver.ad_hoc_run(profile, &name);
+        println!("{}", solver.solve_text(&run, &text, &source)?);
+    }
+    Ok(())
+}
+
+fn verify(registry: &Registry, args: RunArgs) -> anyhow::Result<()> {
+    let mut history = History::start();
+    let mut summary = Summary::default();
+    args.for_each_outcome(registry, |solver, run, outcome| {
+        let name = format!("{}/{} (part {})", solver.dir(), run.input, solver.part);
+        let actual = match outcome {
+            Outcome::Done(answer, timings) => {
+                history.add(&answer, Measurement::new(solver, run, 1, timings));
+                answer
+            }
+            outcome => {
+                println!("{:<7} {}: {}", outcome.label(), name, outcome);
+                summary.add(outcome.label());
+                return Ok(());
+            }
+        };
+        match solver.expected(run) {
+            Ok(expected) if actual.matches(&expected) => {
+                println!("ok      {}", name);
+                summary.add("ok");
+            }
+            Ok(expected) => {
+                println!("FAIL    {}: differs from {}", name, run.output);
+                println!("{}", verify::diff(&expected, &actual.to_string()));
+                summary.add("FAIL");
+            }
+            Err(e) => {
+                println!("MISSING {}: can't read {} ({:#})", name, run.output, e);
+                summary.add("MISSING");
+            }
+        }
+        Ok(())
+    })?;
+    history.save()?;
+    println!("{}", summary);
+    if summary.failed() > 0 {
+        bail!("{} of {} runs failed verification", summary.failed(), summary.total());
+    }
+    Ok(())
+}
+
+fn bless(registry: &Registry, args: RunArgs) -> anyhow::Result<()> {
+    let mut summary = Summary::default();
+    args.for_each_outcome(registry, |solver, run, outcome| {
+        let actual = match outcome {
+            Outcome::Done(answer, _) => answer,
+            outcome => {
+                println!("{:<7} {}/{} (part {}): {}", outcome.label(), solver.dir(), run.input, solver.part, outcome);
+                summary.add(outcome.label());
+                return Ok(());
+            }
+        };
+        let old = solver.expected(run).ok();
+        if old.as_deref().is_some_and(|old| actual.matches(old)) {
+            summary.add("ok");
+            return Ok(());
+        }
+        if run.answer.is_some() {
+            println!("FAIL    {}/{} (part {}): the answer is spelled out in {}, edit it by hand",
+                solver.dir(), run.input, solver.part, run.output);
+            summary.add("FAIL");
+            return Ok(());
+        }
+        summary.add("ok");
+        println!("Updating {}/{}", solver.dir(), run.output);
+        fs::write(solver.output_path(run), actual.stored())?;
+        Ok(())
+    })?;
+    if summary.failed() > 0 {
+        bail!("{} of {} runs failed, their .out files were left alone", summary.failed(), summary.total());
+    }
+    Ok(())
+}
+
+fn bench(registry: &Registry, args: BenchArgs) -> anyhow::Result<()> {
+    let mut history = History::start();
+    let mut report = Report { runs: Vec::new(), failures: Vec::new() };
+    for group in registry::groups(&select(registry, &args.selection)?) {
+        for measured in bench::measure(&group, args.repeat, timeout(args.timeout)) {
+            match measured {
+                Ok((answer, measurement)) => {
+                    history.add(&answer, measurement.clone());
+                    report.runs.push(measurement);
+                }
+                Err(failure) => report.failures.push(failure),
+            }
+        }
+    }
+    println!("{}", report.table());
+    if let Some(path) = args.json {
+        fs::write(path, serde_json::to_string_pretty(&report)?)?;
+    }
+    history.save()?;
+    if !report.failures.is_empty() {
+        bail!("{} of {} runs failed", report.failures.len(), report.failures.len() + report.runs.len());
+    }
+    Ok(())
+}
+
+fn compare(registry: &Registry, args: CompareArgs) -> anyhow::Result<()> {
+    let records = history::load()?;
+    let selected = select(registry, &args.selection)?;
+    let mut history = History::start();
+    let mut regressed = 0;
+    let mut failed = 0;
+    let measured: Vec<_> = registry::groups(&selected).iter()
+        .flat_map(|group| bench::measure(group, args.repeat, timeout(args.timeout)))
+        .collect();
+    for measured in measured {
+        let (answer, measurement) = match measured {
+            Ok(measured) => measured,
+            Err(failure) => {
+                println!("{:<7} {}: {}", failure.outcome, failure.name(), failure.message);
+                failed += 1;
+                continue;
+            }
+        };
+        let current = history.add(&answer, measurement);
+        let name = current.measurement.name();
+        let Some(previous) = history::last(&records, current) else {
+            println!("new     {}", name);
+            continue;
+        };
+        let found = history::regressions(previous, current, args.threshold);
+        if found.is_empty() {
+            println!("ok      {}", name);
+            continue;
+        }
+        regressed += 1;
+        println!("REGRESS {}", name);
+        for regression in found {
+            println!("  {}", regression);
+        }
+    }
+    history.save()?;
+    if failed > 0 {
+        bail!("{} of {} runs failed, {} regressed", failed, selected.len(), regressed);
+    }
+    if regressed > 0 {
+        bail!("{} of {} runs regressed", regressed, selected.len());
+    }
+    Ok(())
+}
+
+fn orphans(registry: &Registry) -> anyhow::Result<()> {
+    let mut days: BTreeSet<u32> = registry.solvers.iter().map(|solver| solver.day).collect();
+    days.extend(discover::days(registry::data_root()));
+    let mut found = 0;
+    for day in days {
+        let (dir, data_dir) = (registry::dir(day), registry::data_dir(day));
+        let runs: Vec<_> = registry.solvers.iter()
+            .filter(|solver| solver.day == day)
+            .flat_map(|solver| &solver.runs)
+            .collect();
+        if runs.is_empty() {
+            println!("unsolved {}", dir);
+            found += 1;
+            continue;
+        }
+        for file in runs.iter().flat_map(|run| [&run.input, &run.output]) {
+            if !data_dir.join(file).exists() {
+                println!("missing  {}/{}", dir, file);
+                found += 1;
+            }
+        }
+        for input in discover::inputs(&data_dir) {
+            if !runs.iter().any(|run| run.input == input) {
+                println!("unused   {}/{}", dir, input);
+                found += 1;
+            }
+        }
+        for output in discover::outputs(&data_dir) {
+            if !runs.iter().any(|run| run.output == output) {
+                println!("orphan   {}/{}", dir, output);
+                found += 1;
+            }
+        }
+    }
+    if found > 0 {
+        bail!("{} data files are not paired up", found);
+    }
+    println!("every data file is used");
+    Ok(())
+}
+
+fn lint_inputs(registry: &Registry) -> anyhow::Result<()> {
+    let mut days: BTreeSet<u32> = registry.solvers.iter().map(|solver| solver.day).collect();
+    days.extend(discover::days(registry::data_root()));
+    let mut found = 0;
+    for day in days {
+        let data_dir = registry::data_dir(day);
+        for input in discover::inputs(&data_dir) {
+            let text = fs::read_to_string(data_dir.join(&input))?;
+            let deviations = normalize::deviations(&text);
+            if !deviations.is_empty() {
+                println!("{}/{}: {}", registry::dir(day), input, deviations.join(", "));
+                found += 1;
+            }
+        }
+    }
+    if found > 0 {
+        bail!("{} input files are not normalized, solvers still get them cleaned up", found);
+    }
+    println!("every input file is normalized");
+    Ok(())
+}
+
+/// Parses the command line, registers the solutions with `register` and runs
+/// the requested command against them.
+pub fn main(about: &'static str, template: &Template, register: impl FnOnce(&mut Registry)) -> anyhow::Result<()> {
+    let matches = Cli::command().about(about).get_matches();
+    let cli = Cli::from_arg_matches(&matches)?;
+    log::configure(cli.verbosity.level(), &cli.verbosity.trace);
+    // Runs are looked up in the data directory as they are registered.
+    registry::set_data_root(cli.data.clone());
+    let mut registry = Registry::default();
+    register(&mut registry);
+    for warning in &registry.warnings {
+        eprintln!("warning: {}", warning);
+    }
+    match cli.command {
+        Command::Run(RunCommand { args, file: Some(file) }) => run_file(&registry, &args.selection, &file),
+        Command::Run(RunCommand { args, file: None }) => run(&registry, args),
+        Command::Verify(args) => verify(&registry, args),
+        Command::Bless(args) => bless(&registry, args),
+        Command::Bench(args) => bench(&registry, args),
+        Command::Compare(args) => compare(&registry, args),
+        Command::Orphans => orphans(&registry),
+        Command::LintInputs => lint_inputs(&registry),
+        Command::Watch(args) => {
+            let mut forwarded = cli.verbosity.args();
+            forwarded.push(format!("--data={}", cli.data.display()));
+            watch::watch(args.day, args.timeout, args.jobs, &forwarded)
+        }
+        Command::NewDay { day } => {
+            if registry.solvers.iter().any(|solver| solver.day == day) {
+                bail!("day {} is already registered", day);
+            }
+            scaffold::new_day(template, day)
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Pairs the inputs in a `data/problemNN` directory with expected outputs by
+//! file name:
+//!
+//! * `X-partN.in` and `X-N.in` are inputs for part N only, answered by `X-partN.out`
+//!   and `X-N.out`.
+//! * Any other `X.in` is answered by `X-partN.out` or `X-N.out` for part N, and
+//!   also by plain `X.out` for part 1. Outputs already claimed by a part-specific
+//!   input don't count.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+/// The part an input is dedicated to, if its name says so.
+fn dedicated_part(stem: &str) -> Option<u32> {
+    let (_, suffix) = stem.rsplit_once('-')?;
+    suffix.strip_prefix("part").unwrap_or(suffix).parse().ok()
+}
+
+fn file_names(dir: &Path, extension: &str) -> Vec<String> {
+    let pattern = dir.join(format!("*.{}", extension));
+    let Ok(paths) = glob::glob(&pattern.to_string_lossy()) else {
+        return Vec::new();
+    };
+    let mut names: Vec<_> = paths.flatten()
+        .filter_map(|path| Some(path.file_name()?.to_str()?.to_string()))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Days that have a `problemNN` directory under `root`.
+pub(crate) fn days(root: &Path) -> Vec<u32> {
+    let Ok(paths) = glob::glob(&root.join("problem*").to_string_lossy()) else {
+        return Vec::new();
+    };
+    paths.flatten()
+        .filter(|path| path.is_dir())
+        .filter_map(|path| path.file_name()?.to_str()?.strip_prefix("problem")?.parse().ok())
+        .collect()
+}
+
+pub(crate) fn inputs(dir: &Path) -> Vec<String> {
+    file_names(dir, "in")
+}
+
+pub(crate) fn outputs(dir: &Path) -> Vec<String> {
+    file_names(dir, "out")
+}
+
+/// `(input, expected output)` pairs for `part` found in `dir`.
+pub(crate) fn runs(dir: &Path, part: u32) -> Vec<(String, String)> {
+    pair(&inputs(dir), &outputs(dir), part)
+}
+
+fn pair(inputs: &[String], outputs: &[String], part: u32) -> Vec<(String, String)> {
+    let outputs: HashSet<&str> = outputs.iter().map(String::as_str).collect();
+    let stems: Vec<_> = inputs.iter().filter_map(|input| input.strip_suffix(".in")).collect();
+    let claimed: HashSet<String> = stems.iter()
+        .filter(|stem| dedicated_part(stem).is_some())
+        .map(|stem| format!("{}.out", stem))
+        .collect();
+    let mut runs = Vec::new();
+    for stem in stems {
+        let candidates = match dedicated_part(stem) {
+            Some(p) if p == part => vec![format!("{}.out", stem)],
+            Some(_) => continue,
+            None => {
+                let mut candidates = vec![format!("{}-part{}.out", stem, part), format!("{}-{}.out", stem, part)];
+                if part == 1 {
+                    candidates.push(format!("{}.out", stem));
+                }
+                candidates.retain(|c| !claimed.contains(c));
+                candidates
+            }
+        };
+        if let Some(output) = candidates.into_iter().find(|c| outputs.contains(c.as_str())) {
+            runs.push((format!("{}.in", stem), output));
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::discover::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn paired(inputs: &[&str], outputs: &[&str], part: u32) -> Vec<(String, String)> {
+        pair(&names(inputs), &names(outputs), part)
+    }
+
+    #[test]
+    fn both_naming_styles_are_recognised() {
+        let inputs = ["01.in", "sample.in"];
+        assert_eq!(paired(&inputs, &["01-part1.out", "sample-part1.out"], 1), vec![
+            ("01.in".to_string(), "01-part1.out".to_string()),
+            ("sample.in".to_string(), "sample-part1.out".to_string()),
+        ]);
+        assert_eq!(paired(&inputs, &["01.out", "01-2.out", "sample.out"], 2), vec![
+            ("01.in".to_string(), "01-2.out".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn dedicated_inputs_claim_their_output() {
+        let inputs = ["sample.in", "sample-2.in"];
+        let outputs = ["sample.out", "sample-2.out"];
+        assert_eq!(paired(&inputs, &outputs, 1), vec![("sample.in".to_string(), "sample.out".to_string())]);
+        assert_eq!(paired(&inputs, &outputs, 2), vec![("sample-2.in".to_string(), "sample-2.out".to_string())]);
+    }
+}
@@ -0,0 +1,401 @@
+//! Integer points, vectors and compass directions.
+//!
+//! Coordinates are screen-like: `x` grows to the right and `y` grows down, so
+//! "up" is `-y` and a point `(x, y)` on a map is the grid position
+//! `(row, col) = (y, x)`. `Point::to_pos` and `Point::from_pos` convert
+//! between the two, and `Direction::delta` is ready for `Grid::offset`.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::grid::Pos;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// The difference between two `Point`s.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+/// The difference between two `Point3`s.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vector3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+/// Point and vector arithmetic, the same for any number of dimensions.
+macro_rules! coordinates {
+    ($point:ident, $vector:ident, $($axis:ident),+) => {
+        impl $point {
+            pub const ORIGIN: $point = $point { $($axis: 0),+ };
+
+            pub const fn new($($axis: i64),+) -> Self {
+                $point { $($axis),+ }
+            }
+
+            /// Distance when moving along one axis at a time.
+            pub fn manhattan(self, other: $point) -> i64 {
+                (self - other).manhattan()
+            }
+
+            /// Distance when diagonal moves cost the same as straight ones.
+            pub fn chebyshev(self, other: $point) -> i64 {
+                (self - other).chebyshev()
+            }
+
+            /// Squared euclidean distance, which orders points the same way
+            /// as the real one without leaving the integers.
+            pub fn dist2(self, other: $point) -> i64 {
+                (self - other).len2()
+            }
+        }
+
+        impl $vector {
+            pub const ZERO: $vector = $vector { $($axis: 0),+ };
+
+            pub const fn new($($axis: i64),+) -> Self {
+                $vector { $($axis),+ }
+            }
+
+            pub fn manhattan(self) -> i64 {
+                0 $(+ self.$axis.abs())+
+            }
+
+            pub fn chebyshev(self) -> i64 {
+                0 $(.max(self.$axis.abs()))+
+            }
+
+            /// Squared length.
+            pub fn len2(self) -> i64 {
+                0 $(+ self.$axis * self.$axis)+
+            }
+        }
+
+        impl Add<$vector> for $point {
+            type Output = $point;
+
+            fn add(self, v: $vector) -> $point {
+                $point { $($axis: self.$axis + v.$axis),+ }
+            }
+        }
+
+        impl AddAssign<$vector> for $point {
+            fn add_assign(&mut self, v: $vector) {
+                *self = *self + v;
+            }
+        }
+
+        impl Sub<$vector> for $point {
+            type Output = $point;
+
+            fn sub(self, v: $vector) -> $point {
+                $point { $($axis: self.$axis - v.$axis),+ }
+            }
+        }
+
+        impl SubAssign<$vector> for $point {
+            fn sub_assign(&mut self, v: $vector) {
+                *self = *self - v;
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $vector;
+
+            fn sub(self, other: $point) -> $vector {
+                $vector { $($axis: self.$axis - other.$axis),+ }
+            }
+        }
+
+        impl Add for $vector {
+            type Output = $vector;
+
+            fn add(self, v: $vector) -> $vector {
+                $vector { $($axis: self.$axis + v.$axis),+ }
+            }
+        }
+
+        impl AddAssign for $vector {
+            fn add_assign(&mut self, v: $vector) {
+                *self = *self + v;
+            }
+        }
+
+        impl Sub for $vector {
+            type Output = $vector;
+
+            fn sub(self, v: $vector) -> $vector {
+                $vector { $($axis: self.$axis - v.$axis),+ }
+            }
+        }
+
+        impl SubAssign for $vector {
+            fn sub_assign(&mut self, v: $vector) {
+                *self = *self - v;
+            }
+        }
+
+        impl Neg for $vector {
+            type Output = $vector;
+
+            fn neg(self) -> $vector {
+                $vector { $($axis: -self.$axis),+ }
+            }
+        }
+
+        impl Mul<i64> for $vector {
+            type Output = $vector;
+
+            fn mul(self, k: i64) -> $vector {
+                $vector { $($axis: self.$axis * k),+ }
+            }
+        }
+    };
+}
+
+coordinates!(Point, Vector, x, y);
+coordinates!(Point3, Vector3, x, y, z);
+
+impl Point {
+    /// The grid position of this point, unless it's left of or above the
+    /// grid. Whether it's inside is up to `Grid::get`.
+    pub fn to_pos(self) -> Option<Pos> {
+        Some((usize::try_from(self.y).ok()?, usize::try_from(self.x).ok()?))
+    }
+
+    pub fn from_pos((row, col): Pos) -> Self {
+        let coordinate = |i: usize| i64::try_from(i).expect("grid position fits i64");
+        Point { x: coordinate(col), y: coordinate(row) }
+    }
+
+    /// The 4 points sharing a side with this one, clockwise from up.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |dir| self + dir.vector())
+    }
+
+    /// The 8 points sharing a side or a corner with this one, clockwise from up.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Direction8::ALL.into_iter().map(move |dir| self + dir.vector())
+    }
+}
+
+impl Vector {
+    /// The vector turned a quarter clockwise, as seen on screen.
+    pub fn clockwise(self) -> Self {
+        Vector { x: -self.y, y: self.x }
+    }
+
+    pub fn counter_clockwise(self) -> Self {
+        Vector { x: self.y, y: -self.x }
+    }
+
+    /// The `(drow, dcol)` pair `Grid::offset` takes.
+    pub fn delta(self) -> (isize, isize) {
+        let delta = |d: i64| isize::try_from(d).expect("grid offset fits isize");
+        (delta(self.y), delta(self.x))
+    }
+}
+
+/// One of the 4 directions along the grid lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise from up.
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    /// The direction an arrow out of `^>v<` points to.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+
+    /// A step of length one in this direction.
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction::Up => Vector::new(0, -1),
+            Direction::Right => Vector::new(1, 0),
+            Direction::Down => Vector::new(0, 1),
+            Direction::Left => Vector::new(-1, 0),
+        }
+    }
+
+    /// The `(drow, dcol)` pair `Grid::offset` takes.
+    pub fn delta(self) -> (isize, isize) {
+        self.vector().delta()
+    }
+
+    /// Turned by `quarters` quarter turns clockwise, or counter-clockwise
+    /// when negative.
+    pub fn rotate(self, quarters: i32) -> Self {
+        Self::ALL[(self as i32 + quarters).rem_euclid(4) as usize]
+    }
+
+    pub fn clockwise(self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn counter_clockwise(self) -> Self {
+        self.rotate(-1)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(2)
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+}
+
+/// One of the 8 directions along the grid lines and diagonals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// Clockwise from up.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    /// A step to the next point in this direction, diagonals included.
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction8::Up => Vector::new(0, -1),
+            Direction8::UpRight => Vector::new(1, -1),
+            Direction8::Right => Vector::new(1, 0),
+            Direction8::DownRight => Vector::new(1, 1),
+            Direction8::Down => Vector::new(0, 1),
+            Direction8::DownLeft => Vector::new(-1, 1),
+            Direction8::Left => Vector::new(-1, 0),
+            Direction8::UpLeft => Vector::new(-1, -1),
+        }
+    }
+
+    /// The `(drow, dcol)` pair `Grid::offset` takes.
+    pub fn delta(self) -> (isize, isize) {
+        self.vector().delta()
+    }
+
+    /// Turned by `eighths` eighth turns clockwise, or counter-clockwise when
+    /// negative.
+    pub fn rotate(self, eighths: i32) -> Self {
+        Self::ALL[(self as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    pub fn clockwise(self) -> Self {
+        self.rotate(1)
+    }
+
+    pub fn counter_clockwise(self) -> Self {
+        self.rotate(-1)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        Direction8::ALL[dir as usize * 2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::*;
+
+    #[test]
+    fn directions_turn_and_point_the_right_way() {
+        assert_eq!(Direction::Up.clockwise(), Direction::Right);
+        assert_eq!(Direction::Up.counter_clockwise(), Direction::Left);
+        assert_eq!(Direction::Left.rotate(5), Direction::Up);
+        assert_eq!(Direction::Down.opposite(), Direction::Up);
+        for dir in Direction::ALL {
+            assert_eq!(dir.clockwise().vector(), dir.vector().clockwise());
+            assert_eq!(dir.counter_clockwise().vector(), dir.vector().counter_clockwise());
+            assert_eq!(Direction::from_arrow(dir.arrow()), Some(dir));
+            assert_eq!(Direction8::from(dir).vector(), dir.vector());
+        }
+        assert_eq!(Direction::Up.delta(), (-1, 0));
+        assert_eq!(Direction::Left.delta(), (0, -1));
+        assert_eq!(Direction8::UpLeft.clockwise(), Direction8::Up);
+        assert_eq!(Direction8::DownRight.opposite(), Direction8::UpLeft);
+        assert!(Direction8::DownLeft.is_diagonal() && !Direction8::Left.is_diagonal());
+    }
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point::new(1, -2), Point::new(-3, 1));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.dist2(b), 25);
+        assert_eq!(b + (a - b), a);
+        assert_eq!(Point3::new(1, 2, 3).dist2(Point3::ORIGIN), 14);
+        assert_eq!(Point3::new(1, 2, 3).manhattan(Point3::new(0, 0, 0)), 6);
+        assert_eq!((Vector3::new(1, -1, 2) * 3).chebyshev(), 6);
+    }
+
+    #[test]
+    fn points_convert_to_grid_positions() {
+        assert_eq!(Point::new(3, 1).to_pos(), Some((1, 3)));
+        assert_eq!(Point::from_pos((1, 3)), Point::new(3, 1));
+        assert_eq!(Point::new(0, -1).to_pos(), None);
+        assert_eq!(Point::ORIGIN.neighbours4().filter_map(Point::to_pos).collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+        assert_eq!(Point::ORIGIN.neighbours8().count(), 8);
+    }
+}
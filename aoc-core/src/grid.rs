@@ -0,0 +1,270 @@
+//! A rectangular map of cells, the shape a lot of puzzle inputs come in.
+//!
+//! Positions are `(row, col)` pairs counted from the top left corner. Moving
+//! around goes through `offset` and the neighbour iterators, which never step
+//! off the map, so solvers don't need bounds checks of their own.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::geometry::{Direction, Direction8};
+use crate::parse::ParseError;
+
+/// A `(row, col)` position on a grid.
+pub type Pos = (usize, usize);
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+/// Where the marker characters of a map were, see `Grid::parse_marked`.
+#[derive(Clone, Debug, Default)]
+pub struct Markers(Vec<(char, Pos)>);
+
+impl Markers {
+    /// Every position of `marker`, row by row.
+    pub fn all(&self, marker: char) -> impl Iterator<Item = Pos> + '_ {
+        self.0.iter().filter(move |(c, _)| *c == marker).map(|&(_, pos)| pos)
+    }
+
+    /// The position of a marker that appears exactly once, like a start tile.
+    pub fn one(&self, marker: char) -> anyhow::Result<Pos> {
+        let mut found = self.all(marker);
+        match (found.next(), found.next()) {
+            (Some(pos), None) => Ok(pos),
+            (None, _) => anyhow::bail!("no `{}` on the map", marker),
+            (Some(_), Some(_)) => anyhow::bail!("more than one `{}` on the map", marker),
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid { rows, cols, cells: vec![fill; rows * cols] }
+    }
+
+    /// Parses one row per line of `text`, turning each character into a cell
+    /// with `cell`. A character `cell` has no cell for, or a row longer or
+    /// shorter than the first one, is an error expecting `expected`.
+    pub fn parse(text: &str, expected: &str, cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        Self::parse_marked(text, expected, "", cell).map(|(grid, _)| grid)
+    }
+
+    /// Like `parse`, and also notes where each of the `markers` characters is.
+    /// Markers still go through `cell`, which says what lies under them.
+    pub fn parse_marked(text: &str, expected: &str, markers: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<(Self, Markers), ParseError> {
+        let mut cells = Vec::new();
+        let mut found = Vec::new();
+        let mut cols = None;
+        let mut rows = 0;
+        for (row, line) in text.lines().enumerate() {
+            let mut width = 0;
+            for (col, (offset, c)) in line.char_indices().enumerate() {
+                cells.push(cell(c).ok_or_else(|| ParseError::at(text, &line[offset..], expected))?);
+                if markers.contains(c) {
+                    found.push((c, (row, col)));
+                }
+                width += 1;
+            }
+            let cols = *cols.get_or_insert(width);
+            if width != cols {
+                let at = line.char_indices().nth(cols).map_or(line.len(), |(offset, _)| offset);
+                return Err(ParseError::at(text, &line[at..], format!("a row of {} cells", cols)));
+            }
+            rows += 1;
+        }
+        Ok((Grid { rows, cols: cols.unwrap_or(0), cells }, Markers(found)))
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, (row, col): Pos) -> Option<&T> {
+        (row < self.rows && col < self.cols).then(|| &self.cells[row * self.cols + col])
+    }
+
+    pub fn get_mut(&mut self, (row, col): Pos) -> Option<&mut T> {
+        (row < self.rows && col < self.cols).then(|| &mut self.cells[row * self.cols + col])
+    }
+
+    /// `pos` moved by `(drow, dcol)`, if that's still on the grid.
+    pub fn offset(&self, (row, col): Pos, (drow, dcol): (isize, isize)) -> Option<Pos> {
+        let row = row.checked_add_signed(drow).filter(|&row| row < self.rows)?;
+        let col = col.checked_add_signed(dcol).filter(|&col| col < self.cols)?;
+        Some((row, col))
+    }
+
+    /// The up to 4 positions sharing a side with `pos`, clockwise from up.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL.iter().filter_map(move |dir| self.offset(pos, dir.delta()))
+    }
+
+    /// The up to 8 positions sharing a side or a corner with `pos`, clockwise from up.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction8::ALL.iter().filter_map(move |dir| self.offset(pos, dir.delta()))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + use<T> {
+        let cols = self.cols;
+        (0..self.rows * cols).map(move |i| (i / cols, i % cols))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The position of the first cell, row by row, that `pred` holds for.
+    pub fn find(&self, mut pred: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, cell)| pred(cell)).map(|(pos, _)| pos)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(col < self.cols, "column {} of a grid with {} columns", col, self.cols);
+        self.cells.iter().skip(col).step_by(self.cols)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { rows: self.rows, cols: self.cols, cells: self.cells.iter().map(f).collect() }
+    }
+
+    /// The grid turned a quarter clockwise: the first column, read bottom up,
+    /// becomes the first row.
+    pub fn rotate(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.cols)
+            .flat_map(|col| (0..self.rows).rev().map(move |row| (row, col)))
+            .map(|pos| self[pos].clone())
+            .collect();
+        Grid { rows: self.cols, cols: self.rows, cells }
+    }
+
+    /// The grid flipped left to right.
+    pub fn mirror(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.rows).flat_map(|row| self.row(row).iter().rev().cloned()).collect();
+        Grid { rows: self.rows, cols: self.cols, cells }
+    }
+
+    /// Draws the grid one character per cell, for debug output and artifacts.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut out = String::with_capacity(self.rows * (self.cols + 1));
+        for row in 0..self.rows {
+            out.extend(self.row(row).iter().map(&mut cell));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl Grid<char> {
+    /// A grid of the characters of `text` as they are.
+    pub fn parse_chars(text: &str) -> Result<Self, ParseError> {
+        Self::parse(text, "any character", Some)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} is off a {}x{} grid", pos, self.rows, self.cols),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (rows, cols) = (self.rows, self.cols);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} is off a {}x{} grid", pos, rows, cols),
+        }
+    }
+}
+
+/// One line per row, cells written next to each other.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.rows {
+            for cell in self.row(row) {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::*;
+
+    #[test]
+    fn maps_parse_with_their_markers() {
+        let (grid, markers) = Grid::parse_marked("#S.\n.E#\n", "one of #.SE", "SE", |c| match c {
+            '#' => Some(false),
+            '.' | 'S' | 'E' => Some(true),
+            _ => None,
+        }).unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(markers.one('S').unwrap(), (0, 1));
+        assert_eq!(markers.one('E').unwrap(), (1, 1));
+        assert!(markers.one('X').is_err());
+        assert_eq!(grid.render(|&open| if open { '.' } else { '#' }), "#..\n..#\n");
+    }
+
+    #[test]
+    fn bad_maps_point_at_the_problem() {
+        let e = Grid::parse("#.\n#x\n", "one of #.", |c| (c == '#' || c == '.').then_some(c)).unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 2, "one of #."));
+        let e = Grid::parse_chars("abc\nab\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 3, "a row of 3 cells"));
+    }
+
+    #[test]
+    fn neighbours_stay_on_the_grid() {
+        let grid = Grid::new(3, 4, 0);
+        assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8((2, 3)).collect::<Vec<_>>(), vec![(1, 3), (2, 2), (1, 2)]);
+        assert_eq!(grid.offset((2, 3), (0, 1)), None);
+        assert_eq!(grid.get((3, 0)), None);
+    }
+
+    #[test]
+    fn views_and_transforms() {
+        // ABC  -> DA
+        // DEF     EB
+        //         FC
+        let grid = Grid::parse_chars("ABC\nDEF\n").unwrap();
+        assert_eq!(grid.rotate(), Grid::parse_chars("DA\nEB\nFC\n").unwrap());
+        assert_eq!(grid.mirror().to_string(), "CBA\nFED\n");
+        assert_eq!(grid.row(1), &['D', 'E', 'F']);
+        assert_eq!(grid.column(2).collect::<String>(), "CF");
+        assert_eq!(grid.find(|&c| c == 'E'), Some((1, 1)));
+        assert_eq!(grid.map(|c| c.is_ascii_lowercase()).positions().count(), 6);
+    }
+}
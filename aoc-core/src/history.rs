@@ -0,0 +1,137 @@
+//! Answers and timings of past runs, one JSON record per line in
+//! `.aoc-history.jsonl` inside the data root. Records only name a day, part
+//! and input, so each data root (and so each year) keeps a history of its own.
+
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::answer::Answer;
+use crate::bench::{format_ns, Measurement};
+use crate::registry;
+
+fn path() -> PathBuf {
+    registry::data_root().join(".aoc-history.jsonl")
+}
+
+/// Slowdowns smaller than this are noise no matter the ratio.
+const NOISE_NS: u64 = 1_000_000;
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Record {
+    pub(crate) commit: String,
+    pub(crate) timestamp: u64,
+    #[serde(flatten)]
+    pub(crate) measurement: Measurement,
+    pub(crate) answer: String,
+}
+
+impl Record {
+    fn same_run(&self, other: &Record) -> bool {
+        let (a, b) = (&self.measurement, &other.measurement);
+        (a.day, a.part, &a.input) == (b.day, b.part, &b.input)
+    }
+}
+
+/// Collects the records of one invocation and appends them on `save`.
+pub(crate) struct History {
+    commit: String,
+    timestamp: u64,
+    records: Vec<Record>,
+}
+
+impl History {
+    pub(crate) fn start() -> Self {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        History { commit: commit(), timestamp, records: Vec::new() }
+    }
+
+    pub(crate) fn add(&mut self, answer: &Answer, measurement: Measurement) -> &Record {
+        self.records.push(Record {
+            commit: self.commit.clone(),
+            timestamp: self.timestamp,
+            measurement,
+            answer: answer.to_string(),
+        });
+        self.records.last().unwrap()
+    }
+
+    pub(crate) fn save(&self) -> anyhow::Result<()> {
+        let path = path();
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)
+            .with_context(|| format!("can't open {}", path.display()))?;
+        for record in &self.records {
+            writeln!(file, "{}", serde_json::to_string(record)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// `git describe` of the working tree, with `-dirty` when there are local changes.
+fn commit() -> String {
+    Command::new("git").args(["describe", "--always", "--dirty"]).output().ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map_or_else(|| "unknown".to_string(), |s| s.trim().to_string())
+}
+
+pub(crate) fn load() -> anyhow::Result<Vec<Record>> {
+    let path = path();
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("can't read {}", path.display())),
+    };
+    text.lines().enumerate().map(|(i, line)| {
+        serde_json::from_str(line).with_context(|| format!("{}:{}: malformed record", path.display(), i + 1))
+    }).collect()
+}
+
+/// The most recent record of the same day, part and input as `current`.
+pub(crate) fn last<'a>(records: &'a [Record], current: &Record) -> Option<&'a Record> {
+    records.iter().rev().find(|r| r.same_run(current))
+}
+
+/// Describes how `current` regressed against `previous`, if it did.
+pub(crate) fn regressions(previous: &Record, current: &Record, threshold: f64) -> Vec<String> {
+    let mut found = Vec::new();
+    if previous.answer != current.answer {
+        found.push(format!("answer changed since {}: {:?} -> {:?}", previous.commit, previous.answer, current.answer));
+    }
+    let (before, after) = (previous.measurement.total_ns(), current.measurement.total_ns());
+    if after > before + NOISE_NS && after as f64 > before as f64 * threshold {
+        found.push(format!("slower since {}: {} -> {} (x{:.2})",
+            previous.commit, format_ns(before), format_ns(after), after as f64 / before as f64));
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bench::Measurement;
+    use crate::history::*;
+
+    fn record(answer: &str, total_ms: u64) -> Record {
+        let measurement = Measurement {
+            day: 1, part: 1, input: "01.in".to_string(), repeat: 1,
+            parse_ns: 0, solve_ns: total_ms * 1_000_000,
+        };
+        Record { commit: "abc1234".to_string(), timestamp: 0, measurement, answer: answer.to_string() }
+    }
+
+    #[test]
+    fn flags_changed_answers_and_slowdowns() {
+        assert!(regressions(&record("42", 10), &record("42", 12), 1.3).is_empty());
+        assert_eq!(regressions(&record("42", 10), &record("43", 10), 1.3).len(), 1);
+        assert_eq!(regressions(&record("42", 10), &record("42", 20), 1.3).len(), 1);
+    }
+
+    #[test]
+    fn tiny_slowdowns_are_noise() {
+        assert!(regressions(&record("42", 0), &record("42", 1), 1.3).is_empty());
+    }
+}
@@ -0,0 +1,220 @@
+//! Sets of integers kept as sorted, disjoint runs, for puzzles about ranges
+//! too long to walk one number at a time.
+//!
+//! Runs are inclusive like the `3-5` ranges inputs give, and a run never
+//! touches the next one: inserting `1..=2` and `3..=4` leaves a single
+//! `1..=4`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// The integer types an `IntervalSet` can hold.
+pub trait Integer: Copy + Ord + fmt::Debug {
+    fn checked_succ(self) -> Option<Self>;
+    fn checked_pred(self) -> Option<Self>;
+    /// How many numbers lie between `self` and `other`, `0` when they're equal.
+    fn distance(self, other: Self) -> u128;
+}
+
+macro_rules! integer {
+    ($($t:ty),+) => {
+        $(
+            impl Integer for $t {
+                fn checked_succ(self) -> Option<$t> {
+                    self.checked_add(1)
+                }
+
+                fn checked_pred(self) -> Option<$t> {
+                    self.checked_sub(1)
+                }
+
+                fn distance(self, other: $t) -> u128 {
+                    self.abs_diff(other) as u128
+                }
+            }
+        )+
+    };
+}
+
+integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T = u64> {
+    /// Start to end of each run, both included.
+    runs: BTreeMap<T, T>,
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { runs: BTreeMap::new() }
+    }
+
+    /// Adds every number of `range`, merging it with the runs it overlaps or
+    /// touches. An empty range changes nothing.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        if let Some((&s, &e)) = self.runs.range(..=start).next_back()
+            && e.checked_succ().is_none_or(|after| after >= start)
+        {
+            self.runs.remove(&s);
+            start = s;
+            end = end.max(e);
+        }
+        while let Some((&s, &e)) = self.runs.range(start..).next() {
+            if end.checked_succ().is_some_and(|after| s > after) {
+                break;
+            }
+            self.runs.remove(&s);
+            end = end.max(e);
+        }
+        self.runs.insert(start, end);
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.runs.range(..=x).next_back().is_some_and(|(_, &end)| x <= end)
+    }
+
+    /// How many numbers are in the set. Counted in `u128` so that a set
+    /// covering all of a smaller type still fits; only a set of nearly every
+    /// `u128` or `i128` overflows.
+    pub fn len(&self) -> u128 {
+        self.runs.iter().map(|(&start, &end)| start.distance(end) + 1).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// The runs of consecutive numbers in the set, smallest first.
+    pub fn runs(&self) -> impl DoubleEndedIterator<Item = RangeInclusive<T>> + '_ {
+        self.runs.iter().map(|(&start, &end)| start..=end)
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.runs.first_key_value().map(|(&start, _)| start)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.runs.last_key_value().map(|(_, &end)| end)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.runs());
+        union
+    }
+
+    /// The numbers in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut both = IntervalSet::new();
+        let (mut a, mut b) = (self.runs().peekable(), other.runs().peekable());
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            both.insert(*x.start().max(y.start())..=*x.end().min(y.end()));
+            if x.end() < y.end() {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        both
+    }
+
+    /// The numbers in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut rest = IntervalSet::new();
+        for run in self.runs() {
+            let (start, end) = run.into_inner();
+            // Where the part of `run` not covered yet starts, `None` once
+            // it's all covered.
+            let mut from = Some(start);
+            let before = other.runs.range(..start).next_back().filter(|&(_, &e)| e >= start);
+            for (&s, &e) in before.into_iter().chain(other.runs.range(start..=end)) {
+                if let (Some(f), Some(gap_end)) = (from, s.checked_pred()) {
+                    rest.insert(f..=gap_end);
+                }
+                from = e.checked_succ();
+            }
+            if let Some(f) = from {
+                rest.insert(f..=end);
+            }
+        }
+        rest
+    }
+}
+
+impl<T: Integer> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet::new()
+    }
+}
+
+impl<T: Integer> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, ranges: I) {
+        for range in ranges {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Integer> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(ranges: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(ranges);
+        set
+    }
+}
+
+impl<T: Integer> fmt::Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.runs()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::interval::*;
+
+    #[test]
+    fn overlapping_and_touching_ranges_merge() {
+        let mut set: IntervalSet = [3..=5, 10..=14, 16..=20, 12..=18, 6..=6].into_iter().collect();
+        let (start, end) = (30, 29);
+        set.insert(start..=end);
+        assert_eq!(set.runs().collect::<Vec<_>>(), vec![3..=6, 10..=20]);
+        assert_eq!(set.len(), 15);
+        assert!(set.contains(3) && set.contains(15) && set.contains(20));
+        assert!(!set.contains(2) && !set.contains(7) && !set.contains(21));
+        assert_eq!((set.min(), set.max()), (Some(3), Some(20)));
+        assert!(IntervalSet::<u64>::new().is_empty());
+    }
+
+    #[test]
+    fn ranges_at_the_ends_of_the_type() {
+        let mut set: IntervalSet<u8> = [250..=255, 0..=1].into_iter().collect();
+        set.insert(2..=3);
+        set.insert(255..=255);
+        assert_eq!(set.runs().collect::<Vec<_>>(), vec![0..=3, 250..=255]);
+        let all: IntervalSet<u8> = [0..=255].into_iter().collect();
+        assert_eq!(all.difference(&set).runs().collect::<Vec<_>>(), vec![4..=249]);
+        assert!(set.difference(&all).is_empty());
+        assert_eq!(all.len(), 256);
+        let all: IntervalSet<i8> = [i8::MIN..=i8::MAX].into_iter().collect();
+        assert_eq!(all.len(), 256);
+        let all: IntervalSet<u64> = [0..=u64::MAX].into_iter().collect();
+        assert_eq!(all.len(), u64::MAX as u128 + 1);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: IntervalSet<i32> = [-5..=5, 10..=20].into_iter().collect();
+        let b: IntervalSet<i32> = [0..=12, 15..=15, 19..=30].into_iter().collect();
+        assert_eq!(a.intersection(&b).runs().collect::<Vec<_>>(), vec![0..=5, 10..=12, 15..=15, 19..=20]);
+        assert_eq!(a.difference(&b).runs().collect::<Vec<_>>(), vec![-5..=-1, 13..=14, 16..=18]);
+        assert_eq!(b.difference(&a).runs().collect::<Vec<_>>(), vec![6..=9, 21..=30]);
+        assert_eq!(a.union(&b).runs().collect::<Vec<_>>(), vec![-5..=30]);
+        assert_eq!(a.intersection(&b).len() + a.difference(&b).len(), a.len());
+    }
+}
@@ -0,0 +1,136 @@
+//! Runs a solver on its own thread so that a panic or a runaway solver only
+//! costs that one run. Parsing gets a thread of its own too, its result is
+//! shared by all runs on the same input.
+
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::answer::Answer;
+use crate::problem::Timings;
+use crate::registry::{Run, Solver};
+
+/// Solvers get the kind of stack a main thread would, some of them recurse deeply.
+const STACK_SIZE: usize = 256 << 20;
+
+pub enum Outcome {
+    Done(Answer, Timings),
+    Error(anyhow::Error),
+    Panic(String),
+    Timeout(Duration),
+}
+
+impl Outcome {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Outcome::Done(..) => "ok",
+            Outcome::Error(_) => "ERROR",
+            Outcome::Panic(_) => "PANIC",
+            Outcome::Timeout(_) => "TIMEOUT",
+        }
+    }
+
+    /// The same failure, for each run whose shared parse failed.
+    fn again(&self) -> Outcome {
+        match self {
+            Outcome::Done(answer, timings) => Outcome::Done(answer.clone(), *timings),
+            Outcome::Error(e) => Outcome::Error(anyhow::anyhow!("{:#}", e)),
+            Outcome::Panic(message) => Outcome::Panic(message.clone()),
+            Outcome::Timeout(limit) => Outcome::Timeout(*limit),
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Done(answer, _) => write!(f, "{}", answer),
+            Outcome::Error(e) => write!(f, "{:#}", e),
+            Outcome::Panic(message) => write!(f, "{}", message),
+            Outcome::Timeout(limit) => write!(f, "gave up after {:?}", limit),
+        }
+    }
+}
+
+/// Parses the input of a group of runs (see `registry::groups`) once and
+/// solves each run on it. The parse time is reported with the first run only,
+/// a failed parse fails every run of the group.
+pub(crate) fn solve_group(group: &[(&Solver, &Run)], timeout: Option<Duration>) -> Vec<Outcome> {
+    let (first, first_run) = group[0];
+    let (parsed, parse) = match isolated(first.parse_task(first_run), timeout) {
+        Ok(parsed) => parsed,
+        Err(failure) => return group.iter().map(|_| failure.again()).collect(),
+    };
+    group.iter().enumerate().map(|(i, &(solver, run))| {
+        match isolated(solver.solve_task(run, parsed.clone()), timeout) {
+            Ok((answer, solve)) => {
+                let parse = if i == 0 { parse } else { Duration::ZERO };
+                Outcome::Done(answer, Timings { parse, solve })
+            }
+            Err(failure) => failure,
+        }
+    }).collect()
+}
+
+/// Runs `task` on a thread of its own; anything but a result comes back as
+/// the `Outcome` to report. A task that times out keeps running in the
+/// background until the process exits, there is no way to stop a thread from
+/// the outside.
+pub(crate) fn isolated<T, F>(task: F, timeout: Option<Duration>) -> Result<T, Outcome>
+where
+    T: Send + 'static,
+    F: FnOnce() -> anyhow::Result<T> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    let spawned = thread::Builder::new().stack_size(STACK_SIZE).spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(task));
+        let _ = tx.send(result);
+    });
+    if let Err(e) = spawned {
+        return Err(Outcome::Error(e.into()));
+    }
+    let received = match timeout {
+        Some(limit) => rx.recv_timeout(limit),
+        None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match received {
+        Ok(Ok(Ok(result))) => Ok(result),
+        Ok(Ok(Err(e))) => Err(Outcome::Error(e)),
+        Ok(Err(payload)) => Err(Outcome::Panic(panic_message(payload))),
+        Err(RecvTimeoutError::Timeout) => Err(Outcome::Timeout(timeout.unwrap_or_default())),
+        Err(RecvTimeoutError::Disconnected) => Err(Outcome::Panic("solver thread exited without an answer".to_string())),
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "non-string panic payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::isolate::*;
+
+    fn outcome(task: impl FnOnce() -> anyhow::Result<Answer> + Send + 'static) -> Outcome {
+        match isolated(task, Some(Duration::from_millis(200))) {
+            Ok(answer) => Outcome::Done(answer, Timings::default()),
+            Err(failure) => failure,
+        }
+    }
+
+    #[test]
+    fn panics_and_timeouts_are_reported() {
+        assert_eq!(outcome(|| Ok(Answer::from(42))).to_string(), "42");
+        assert_eq!(outcome(|| anyhow::bail!("bad input")).label(), "ERROR");
+        assert_eq!(outcome(|| panic!("not yet")).to_string(), "not yet");
+        assert_eq!(outcome(|| loop { thread::sleep(Duration::from_secs(1)) }).label(), "TIMEOUT");
+    }
+}
@@ -0,0 +1,34 @@
+//! Shared pieces of the per-year solution crates: the `Problem` trait every
+//! solver implements, a registry of runs, and the command line harness on top.
+
+pub mod answer;
+pub mod artifacts;
+mod bench;
+mod discover;
+pub mod geometry;
+pub mod grid;
+pub mod interval;
+pub mod cli;
+mod history;
+mod isolate;
+pub mod log;
+mod manifest;
+pub mod normalize;
+mod parallel;
+pub mod parse;
+pub mod problem;
+pub mod registry;
+pub mod scaffold;
+pub mod search;
+pub mod testing;
+mod verify;
+mod watch;
+
+pub use answer::Answer;
+pub use grid::Grid;
+pub use interval::IntervalSet;
+pub use isolate::Outcome;
+pub use manifest::Params;
+pub use parse::ParseError;
+pub use problem::{solve, Parse, Problem, Timings};
+pub use registry::{Profile, Registry};
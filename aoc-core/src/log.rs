@@ -0,0 +1,120 @@
+//! Debug output of the solvers, off unless asked for on the command line.
+//!
+//! Solvers use `debug!` and `trace!` like `println!`; lines go to stderr
+//! tagged with the day being solved. `-v` shows debug output of every day,
+//! `-vv` also trace output, and `--trace dayNN` trace output of one day.
+
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::RwLock;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Debug = 1,
+    Trace = 2,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        })
+    }
+}
+
+/// The most verbose level enabled for any day, so that disabled calls in hot
+/// loops cost one atomic load.
+static MAX: AtomicU8 = AtomicU8::new(0);
+static CONFIG: RwLock<Config> = RwLock::new(Config { all: None, days: Vec::new() });
+
+struct Config {
+    all: Option<Level>,
+    days: Vec<(u32, Level)>,
+}
+
+impl Config {
+    fn level(&self, day: Option<u32>) -> Option<Level> {
+        let day = day.and_then(|day| self.days.iter().find(|(d, _)| *d == day).map(|&(_, level)| level));
+        day.max(self.all)
+    }
+}
+
+thread_local! {
+    static DAY: Cell<Option<u32>> = const { Cell::new(None) };
+}
+
+/// Enables `all` for every day and `Trace` for each of `traced`.
+pub(crate) fn configure(all: Option<Level>, traced: &[u32]) {
+    let days: Vec<_> = traced.iter().map(|&day| (day, Level::Trace)).collect();
+    let max = days.iter().map(|&(_, level)| level).chain(all).max();
+    *CONFIG.write().unwrap_or_else(|e| e.into_inner()) = Config { all, days };
+    MAX.store(max.map_or(0, |level| level as u8), Ordering::Relaxed);
+}
+
+/// Tags the output of this thread with `day` until the guard is dropped.
+pub(crate) struct Scope(());
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        DAY.with(|current| current.set(None));
+    }
+}
+
+pub(crate) fn enter(day: u32) -> Scope {
+    DAY.with(|current| current.set(Some(day)));
+    Scope(())
+}
+
+#[doc(hidden)]
+pub fn enabled(level: Level) -> bool {
+    if MAX.load(Ordering::Relaxed) < level as u8 {
+        return false;
+    }
+    let day = DAY.with(Cell::get);
+    CONFIG.read().unwrap_or_else(|e| e.into_inner()).level(day).is_some_and(|enabled| enabled >= level)
+}
+
+#[doc(hidden)]
+pub fn write(level: Level, message: fmt::Arguments) {
+    match DAY.with(Cell::get) {
+        Some(day) => eprintln!("[day{:02} {}] {}", day, level, message),
+        None => eprintln!("[{}] {}", level, message),
+    }
+}
+
+/// Like `println!`, shown with `-v` and printed to stderr.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Debug) {
+            $crate::log::write($crate::log::Level::Debug, format_args!($($arg)*));
+        }
+    };
+}
+
+/// Like `println!`, shown with `-vv` or `--trace dayNN` and printed to stderr.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Trace) {
+            $crate::log::write($crate::log::Level::Trace, format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::log::*;
+
+    #[test]
+    fn days_can_be_traced_on_their_own() {
+        let config = Config { all: Some(Level::Debug), days: vec![(24, Level::Trace)] };
+        assert_eq!(config.level(Some(24)), Some(Level::Trace));
+        assert_eq!(config.level(Some(23)), Some(Level::Debug));
+        assert_eq!(config.level(None), Some(Level::Debug));
+        let config = Config { all: None, days: vec![(24, Level::Trace)] };
+        assert_eq!(config.level(Some(23)), None);
+    }
+}
@@ -0,0 +1,145 @@
+//! `data/problemNN/day.toml`, which lists the inputs of a day together with
+//! the parameters each of them needs and, optionally, the expected answers:
+//!
+//! ```toml
+//! [[input]]
+//! file = "sample.in"
+//! parts = [1]                          # every registered part if left out
+//! params = { width = 11, height = 7 }
+//! answers = { 1 = 12, 2 = [1, 7] }     # the usual .out file if left out
+//! ```
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use anyhow::{bail, Context};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::answer::Answer;
+use crate::discover;
+
+pub(crate) const FILE_NAME: &str = "day.toml";
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    #[serde(default)]
+    input: Vec<Input>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Input {
+    file: String,
+    parts: Option<Vec<u32>>,
+    #[serde(default)]
+    params: toml::Table,
+    #[serde(default)]
+    answers: toml::Table,
+}
+
+/// Puzzle parameters of one input, the `params` table of its manifest entry.
+#[derive(Clone, Debug, Default)]
+pub struct Params(toml::Table);
+
+impl Params {
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> anyhow::Result<T> {
+        let value = self.0.get(key).with_context(|| format!("missing parameter `{}`", key))?;
+        value.clone().try_into().with_context(|| format!("bad value for parameter `{}`", key))
+    }
+
+    /// For parameters that only some inputs override.
+    pub fn get_or<T: DeserializeOwned>(&self, key: &str, default: T) -> anyhow::Result<T> {
+        if self.0.contains_key(key) { self.get(key) } else { Ok(default) }
+    }
+}
+
+/// One input of a part as the manifest describes it.
+pub(crate) struct Entry {
+    pub(crate) input: String,
+    /// The `.out` file holding the expected answer, or the manifest itself.
+    pub(crate) output: String,
+    pub(crate) answer: Option<String>,
+    pub(crate) params: Params,
+}
+
+fn answer_from(value: &toml::Value) -> anyhow::Result<Answer> {
+    match value {
+        toml::Value::String(s) => Ok(Answer::from(s.as_str())),
+        toml::Value::Integer(i) => Ok(Answer::from(*i)),
+        toml::Value::Array(items) => Ok(Answer::List(items.iter().map(answer_from).collect::<anyhow::Result<_>>()?)),
+        other => bail!("unexpected {}", other),
+    }
+}
+
+/// Entries for `part`, or `None` if `dir` has no manifest. Inputs listed for
+/// `part` that have no expected answer are left out and described in the
+/// warnings that come along.
+pub(crate) fn entries(dir: &Path, part: u32) -> anyhow::Result<Option<(Vec<Entry>, Vec<String>)>> {
+    let path = dir.join(FILE_NAME);
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("can't read {}", path.display())),
+    };
+    let manifest: Manifest = toml::from_str(&text).with_context(|| format!("malformed {}", path.display()))?;
+    let outputs = discover::runs(dir, part);
+    let mut entries = Vec::new();
+    let mut warnings = Vec::new();
+    for input in manifest.input {
+        if input.parts.as_ref().is_some_and(|parts| !parts.contains(&part)) {
+            continue;
+        }
+        let answer = input.answers.get(&part.to_string()).map(|answer| {
+            answer_from(answer).map(|answer| answer.to_string()).with_context(|| {
+                format!("{}: answer for {} must be a string, an integer or a list of them", path.display(), input.file)
+            })
+        }).transpose()?;
+        let output = match answer {
+            Some(_) => FILE_NAME.to_string(),
+            None => match outputs.iter().find(|(file, _)| *file == input.file) {
+                Some((_, output)) => output.clone(),
+                None => {
+                    warnings.push(format!("{}: {} has no answer for part {}: no output file and no `answers` entry",
+                        path.display(), input.file, part));
+                    continue;
+                }
+            },
+        };
+        entries.push(Entry { input: input.file, output, answer, params: Params(input.params) });
+    }
+    Ok(Some((entries, warnings)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::manifest::*;
+
+    #[test]
+    fn params_fall_back_to_defaults() {
+        let params = Params(toml::from_str("width = 11").unwrap());
+        assert_eq!(params.get::<u32>("width").unwrap(), 11);
+        assert_eq!(params.get_or("height", 103u32).unwrap(), 103);
+        assert!(params.get::<u32>("height").is_err());
+        assert!(params.get::<String>("width").is_err());
+    }
+
+    #[test]
+    fn inputs_without_answers_are_reported() {
+        let dir = std::env::temp_dir().join(format!("aoc-manifest-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(FILE_NAME), "[[input]]\nfile = \"a.in\"\n\n[[input]]\nfile = \"b.in\"\nanswers = { 2 = 7 }\n").unwrap();
+        for file in ["a.in", "a-part1.out", "b.in"] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+        let (part1, warnings) = entries(&dir, 1).unwrap().unwrap();
+        assert_eq!(part1.iter().map(|e| e.input.as_str()).collect::<Vec<_>>(), vec!["a.in"]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("b.in has no answer for part 1"));
+        let (part2, warnings) = entries(&dir, 2).unwrap().unwrap();
+        assert_eq!((part2.len(), part2[0].answer.as_deref()), (1, Some("7")));
+        assert!(warnings[0].contains("a.in has no answer for part 2"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
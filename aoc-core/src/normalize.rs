@@ -0,0 +1,63 @@
+//! Inputs are handed to parsers in one shape no matter how the file was
+//! saved: `\n` line endings, no trailing whitespace on any line, no blank
+//! lines at the end, and exactly one final newline.
+
+use std::borrow::Cow;
+
+pub fn normalize(text: &str) -> Cow<'_, str> {
+    if deviations(text).is_empty() {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len() + 1);
+    for line in text.split('\n') {
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    let end = out.trim_end().len();
+    out.truncate(end);
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    Cow::Owned(out)
+}
+
+/// What keeps `text` from being in normal form, for `lint-inputs`.
+pub(crate) fn deviations(text: &str) -> Vec<&'static str> {
+    let mut found = Vec::new();
+    if text.contains('\r') {
+        found.push("CRLF line endings");
+    }
+    if text.split('\n').any(|line| line.trim_end_matches('\r') != line.trim_end()) {
+        found.push("trailing whitespace");
+    }
+    if !text.is_empty() && !text.ends_with('\n') {
+        found.push("no final newline");
+    }
+    let body = text.strip_suffix('\n').unwrap_or(text);
+    if !text.is_empty() && body.rsplit('\n').next().is_some_and(|line| line.trim().is_empty()) {
+        found.push("blank lines at the end");
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::normalize::*;
+
+    #[test]
+    fn inputs_are_normalized() {
+        assert_eq!(normalize("1 2\r\n3 4  \r\n\r\n"), "1 2\n3 4\n");
+        assert_eq!(normalize("a\n\nb"), "a\n\nb\n");
+        assert_eq!(normalize("  indented\n"), "  indented\n");
+        assert_eq!(normalize(""), "");
+        assert!(matches!(normalize("a\nb\n"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn deviations_are_named() {
+        assert_eq!(deviations("a\r\nb\r\n"), vec!["CRLF line endings"]);
+        assert_eq!(deviations("a \nb"), vec!["trailing whitespace", "no final newline"]);
+        assert_eq!(deviations("a\n\n"), vec!["blank lines at the end"]);
+        assert!(deviations("a\n\nb\n").is_empty());
+    }
+}
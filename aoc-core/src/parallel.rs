@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Runs `work` on `items` using up to `jobs` threads and hands the results to
+/// `emit` in the order of `items`, each one as soon as all earlier ones are
+/// done. The first error from `emit` stops workers from picking up new items.
+pub(crate) fn for_each_ordered<T, R, W, E>(items: &[T], jobs: usize, work: W, mut emit: E) -> anyhow::Result<()>
+where
+    T: Sync,
+    R: Send,
+    W: Fn(&T) -> R + Sync,
+    E: FnMut(&T, R) -> anyhow::Result<()>,
+{
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    thread::scope(|scope| {
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let tx = tx.clone();
+            let (next, stop, work) = (&next, &stop, &work);
+            scope.spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(i) else { break };
+                    if tx.send((i, work(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+        let mut pending = BTreeMap::new();
+        let mut emitted = 0;
+        for (i, result) in rx {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&emitted) {
+                if let Err(e) = emit(&items[emitted], result) {
+                    stop.store(true, Ordering::Relaxed);
+                    return Err(e);
+                }
+                emitted += 1;
+            }
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::parallel::*;
+
+    #[test]
+    fn results_come_out_in_input_order() {
+        let items: Vec<u64> = (0..8).collect();
+        let mut seen = Vec::new();
+        for_each_ordered(&items, 4, |&i| {
+            thread::sleep(Duration::from_millis(10 * (8 - i)));
+            i * i
+        }, |&i, square| {
+            seen.push((i, square));
+            Ok(())
+        }).unwrap();
+        assert_eq!(seen, items.iter().map(|&i| (i, i * i)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn emit_errors_stop_the_sweep() {
+        let items: Vec<u32> = (0..100).collect();
+        let mut seen = 0;
+        let result = for_each_ordered(&items, 2, |&i| i, |&i, _| {
+            seen += 1;
+            if i == 3 { anyhow::bail!("stop") } else { Ok(()) }
+        });
+        assert!(result.is_err());
+        assert_eq!(seen, 4);
+    }
+}
@@ -0,0 +1,276 @@
+//! Errors that point at the spot in the input where parsing went wrong, and
+//! the pieces most inputs are made of: integers, lists, blank-line separated
+//! blocks and `key: value` lines.
+//!
+//! The parsers are nom parsers over `&str`, so they combine with nom's own;
+//! `parse_all` runs one on a whole input and turns its error into a
+//! `ParseError`.
+
+use std::any::type_name;
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use nom::character::complete::{char, digit1, line_ending, multispace0, one_of, space0, space1};
+use nom::branch::alt;
+use nom::combinator::{cut, eof, map_res, not, opt, peek, recognize};
+use nom::error::ErrorKind;
+use nom::multi::separated_list1;
+use nom::sequence::{separated_pair, terminated};
+use nom::{IResult, Parser};
+
+/// The error nom's `&str` parsers and the ones here fail with.
+pub type NomError<'a> = nom::error::Error<&'a str>;
+
+/// A malformed input. Parsers don't know which file they read, the harness
+/// fills that in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<PathBuf>,
+    /// 1-based.
+    pub line: usize,
+    /// 1-based, in characters.
+    pub column: usize,
+    pub expected: String,
+    /// The offending line, for the caret.
+    text: String,
+}
+
+impl ParseError {
+    /// An error at `column` of `text`, line `line` of the input.
+    pub fn new(line: usize, column: usize, text: &str, expected: impl Into<String>) -> ParseError {
+        ParseError { file: None, line, column, expected: expected.into(), text: text.to_string() }
+    }
+
+    /// An error where `rest`, a slice of `input`, starts: nom-style parsers
+    /// report where they got stuck that way, and so can a parser going through
+    /// `input.lines()`.
+    pub fn at(input: &str, rest: &str, expected: impl Into<String>) -> ParseError {
+        let offset = (rest.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize).min(input.len());
+        let before = &input[..offset];
+        let start = before.rfind('\n').map_or(0, |i| i + 1);
+        let end = input[offset..].find('\n').map_or(input.len(), |i| offset + i);
+        let text = input[start..end].trim_end_matches('\r');
+        ParseError::new(before.matches('\n').count() + 1, before[start..].chars().count() + 1, text, expected)
+    }
+
+    /// Converts the error of a nom parser that was run on `input`.
+    pub fn from_nom(input: &str, error: nom::Err<nom::error::Error<&str>>) -> ParseError {
+        match error {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                ParseError::at(input, e.input, expected(e.code))
+            }
+            nom::Err::Incomplete(_) => ParseError::at(input, &input[input.len()..], "more input"),
+        }
+    }
+
+    /// For parsers that go line by line and so see each line as the whole input.
+    pub fn on_line(self, line: usize) -> ParseError {
+        ParseError { line, ..self }
+    }
+
+    /// For an error from parsing `part`, a slice of `input`, on its own:
+    /// moves it to where `part` is in `input`.
+    pub fn within(self, input: &str, part: &str) -> ParseError {
+        let offset = (part.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize).min(input.len());
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + self.line;
+        let start = before.rfind('\n').map_or(0, |i| i + 1);
+        if self.line > 1 || start == offset {
+            return ParseError { line, ..self };
+        }
+        // The error is on the first line of `part`, which starts mid-line.
+        let end = input[offset..].find('\n').map_or(input.len(), |i| offset + i);
+        let column = before[start..].chars().count() + self.column;
+        ParseError { line, column, text: input[start..end].to_string(), ..self }
+    }
+
+    pub fn in_file(self, file: &Path) -> ParseError {
+        ParseError { file: Some(file.to_path_buf()), ..self }
+    }
+}
+
+/// What a nom parser failing with `kind` was looking for.
+fn expected(kind: ErrorKind) -> String {
+    match kind {
+        // What `line_ending` fails with.
+        ErrorKind::CrLf => "end of line".to_string(),
+        kind => kind.description().to_lowercase(),
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}:{}", file.display(), self.line, self.column)?,
+            None => write!(f, "line {}, column {}", self.line, self.column)?,
+        }
+        let number = self.line.to_string();
+        let margin = " ".repeat(number.len());
+        writeln!(f, ": expected {}", self.expected)?;
+        writeln!(f, "{} |", margin)?;
+        writeln!(f, "{} | {}", number, self.text)?;
+        write!(f, "{} | {}^", margin, " ".repeat(self.column - 1))
+    }
+}
+
+impl Error for ParseError {}
+
+/// Runs `parser` on all of `input`. Anything it leaves but whitespace is an
+/// error, so is anything it fails on.
+pub fn parse_all<'a, O>(input: &'a str, parser: impl Parser<&'a str, Output = O, Error = NomError<'a>>) -> Result<O, ParseError> {
+    let (_, (out, _, _)) = (parser, multispace0, eof).parse_complete(input).map_err(|e| ParseError::from_nom(input, e))?;
+    Ok(out)
+}
+
+/// Digits, read as an integer of any width. No sign is accepted, so this
+/// fails on negative numbers even for signed types.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse).parse(input)
+}
+
+/// Digits with an optional `-` or `+` in front, read as an integer of any
+/// width.
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize((opt(one_of("+-")), digit1)), str::parse).parse(input)
+}
+
+/// One or more `item`s separated by commas, with or without spaces after
+/// them: `1,2,3` or `1, 2, 3`.
+pub fn comma_separated<'a, O>(item: impl Parser<&'a str, Output = O, Error = NomError<'a>>) -> impl Parser<&'a str, Output = Vec<O>, Error = NomError<'a>> {
+    separated_list1((char(','), space0), item)
+}
+
+/// One or more `item`s separated by spaces or tabs.
+pub fn space_separated<'a, O>(item: impl Parser<&'a str, Output = O, Error = NomError<'a>>) -> impl Parser<&'a str, Output = Vec<O>, Error = NomError<'a>> {
+    separated_list1(space1, item)
+}
+
+/// One `item` per line, up to a blank line or the end of the input. The
+/// line ending of the last line is taken too, the blank line isn't. A line
+/// `item` fails on, or doesn't take all of, is an error rather than the end
+/// of the list, so it's reported where `item` got stuck.
+pub fn lines<'a, O>(item: impl Parser<&'a str, Output = O, Error = NomError<'a>>) -> impl Parser<&'a str, Output = Vec<O>, Error = NomError<'a>> {
+    let another_line = terminated(line_ending, not(alt((line_ending, eof))));
+    let whole_line = terminated(item, peek(alt((eof, line_ending))));
+    terminated(separated_list1(another_line, cut(whole_line)), opt(line_ending))
+}
+
+/// `key`, a colon, maybe some spaces and `value`, as in `x00: 1`.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<&'a str, Output = K, Error = NomError<'a>>,
+    value: impl Parser<&'a str, Output = V, Error = NomError<'a>>,
+) -> impl Parser<&'a str, Output = (K, V), Error = NomError<'a>> {
+    separated_pair(key, (char(':'), space0), value)
+}
+
+/// Every integer in `text`, whatever is around them: `p=-3,4 v=1,-2` has
+/// -3, 4, 1 and -2. A `-` right after a letter or digit separates rather
+/// than negates, so `11-22` is 11 and 22. A number that doesn't fit `T` is
+/// an error.
+pub fn integers<T: FromStr>(text: &str) -> Result<Vec<T>, ParseError> {
+    let mut found = Vec::new();
+    let mut end = 0;
+    while let Some(at) = text[end..].find(|c: char| c.is_ascii_digit()).map(|i| end + i) {
+        let signed = text[..at].ends_with('-') && !text[..at - 1].ends_with(|c: char| c.is_alphanumeric());
+        let start = if signed { at - 1 } else { at };
+        end = text[at..].find(|c: char| !c.is_ascii_digit()).map_or(text.len(), |i| at + i);
+        let number = &text[start..end];
+        found.push(number.parse().map_err(|_| ParseError::at(text, number, format!("a number that fits {}", type_name::<T>())))?);
+    }
+    Ok(found)
+}
+
+/// The blank-line separated blocks of `text`. They are slices of it, so
+/// errors found in a block can be moved back with `ParseError::within`.
+pub fn blocks(text: &str) -> impl Iterator<Item = &str> {
+    text.split("\n\n").map(|block| block.trim_start_matches('\n')).filter(|block| !block.is_empty())
+}
+
+/// Adds the input file to a `ParseError` inside `error`, leaves other errors alone.
+pub(crate) fn in_file(error: anyhow::Error, file: &Path) -> anyhow::Error {
+    match error.downcast::<ParseError>() {
+        Ok(e) => e.in_file(file).into(),
+        Err(error) => error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::bytes::complete::tag;
+    use nom::character::complete::{alphanumeric1, digit1};
+    use nom::Parser;
+    use crate::grid::Grid;
+    use crate::parse::*;
+
+    #[test]
+    fn errors_point_at_the_stray_character() {
+        let input = "p=0,4\np=6,x\n";
+        let e = ParseError::at(input, &input[10..], "digit").in_file(Path::new("01.in"));
+        assert_eq!((e.line, e.column), (2, 5));
+        let line = input.lines().nth(1).unwrap();
+        assert_eq!(ParseError::at(input, &line[4..], "digit").to_string(), "line 2, column 5: expected digit\n  |\n2 | p=6,x\n  |     ^");
+        assert_eq!(e.to_string(), "01.in:2:5: expected digit\n  |\n2 | p=6,x\n  |     ^");
+    }
+
+    #[test]
+    fn nom_errors_convert() {
+        let input = "x=12";
+        let e = (tag::<_, _, nom::error::Error<&str>>("x="), tag(",")).parse(input).unwrap_err();
+        let e = ParseError::from_nom(input, e);
+        assert_eq!((e.line, e.column, e.expected.as_str()), (1, 3, "tag"));
+        let e = digit1::<_, nom::error::Error<&str>>.parse("").unwrap_err();
+        assert_eq!(ParseError::from_nom("", e).column, 1);
+    }
+
+    #[test]
+    fn integers_of_any_width_and_sign() {
+        assert_eq!(unsigned::<u8>("255,"), Ok((",", 255)));
+        assert!(unsigned::<u8>("256").is_err());
+        assert!(unsigned::<i64>("-1").is_err());
+        assert_eq!(signed::<i64>("-12 "), Ok((" ", -12)));
+        assert_eq!(signed::<i32>("+7"), Ok(("", 7)));
+        assert!(signed::<i32>("1-2").is_ok_and(|(rest, n)| rest == "-2" && n == 1));
+        assert!(signed::<i32>("-").is_err());
+    }
+
+    #[test]
+    fn lists_lines_and_keys() {
+        assert_eq!(parse_all("1, 2,3\n", comma_separated(unsigned::<u32>)), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_all("1  -2\t3", space_separated(signed::<i32>)), Ok(vec![1, -2, 3]));
+        let text = "x00: 1\ny01: 0\n\n5\n";
+        let (rest, wires) = lines(key_value(alphanumeric1, unsigned::<u8>)).parse(text).unwrap();
+        assert_eq!((rest, wires), ("\n5\n", vec![("x00", 1), ("y01", 0)]));
+        let e = parse_all("1,2,x\n", comma_separated(unsigned::<u32>)).unwrap_err();
+        assert_eq!((e.line, e.column), (1, 4));
+        let e = parse_all("1|2\n3-4\n", lines((unsigned::<u32>, char('|'), unsigned::<u32>))).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 2));
+        let e = parse_all("1 2\n3 4 x 5\n6\n", lines(space_separated(unsigned::<u32>))).unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (2, 4, "end of line"));
+    }
+
+    #[test]
+    fn integers_in_free_text() {
+        assert_eq!(integers::<i64>("p=-3,4 v=1,-2"), Ok(vec![-3, 4, 1, -2]));
+        assert_eq!(integers::<u64>("11-22,95-115"), Ok(vec![11, 22, 95, 115]));
+        assert_eq!(integers::<u8>("Button A: X+94"), Ok(vec![94]));
+        let e = integers::<u8>("a 1 300").unwrap_err();
+        assert_eq!((e.column, e.expected.as_str()), (5, "a number that fits u8"));
+        assert_eq!(integers::<i32>("-5"), Ok(vec![-5]));
+    }
+
+    #[test]
+    fn blocks_keep_their_place_in_the_input() {
+        let input = "ab\ncd\n\nef\nx\n";
+        let found: Vec<_> = blocks(input).collect();
+        assert_eq!(found, vec!["ab\ncd", "ef\nx\n"]);
+        let e = Grid::parse_chars(found[1]).unwrap_err().within(input, found[1]);
+        assert_eq!((e.line, e.column), (5, 2));
+        let line = &input[3..];
+        let e = ParseError::at(line, &line[1..], "x").within(input, line);
+        assert_eq!((e.line, e.column), (2, 2));
+        let e = ParseError::at("cd", &"cd"[1..], "x").within(input, &input[4..6]);
+        assert_eq!((e.line, e.column, e.to_string().lines().nth(2)), (2, 3, Some("2 | cd")));
+    }
+}
@@ -0,0 +1,57 @@
+use std::fs;
+use std::io::BufRead;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use anyhow::Context;
+
+use crate::normalize::normalize;
+use crate::parse;
+use crate::Answer;
+
+/// A parsed puzzle input. Parsing belongs to the input rather than to a part,
+/// so every part of a day that takes the same input type can share one value.
+pub trait Parse: Sized {
+    fn parse_from<R: BufRead>(buf: R) -> anyhow::Result<Self>;
+}
+
+/// One part of a day. The harness parses each input file once for all parts
+/// registered for that day with the same `Input` type.
+pub trait Problem {
+    type Input: Parse;
+    type Output: Into<Answer>;
+
+    fn solve(&self, input: &Self::Input) -> Self::Output;
+}
+
+/// Time spent in each phase of one run. Reading and normalizing the input file
+/// count as parsing.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+pub(crate) fn read(in_filename: &Path) -> anyhow::Result<String> {
+    fs::read_to_string(in_filename).with_context(|| format!("can't read {}", in_filename.display()))
+}
+
+pub(crate) fn parse_text<I: Parse>(text: &str) -> anyhow::Result<I> {
+    I::parse_from(normalize(text).as_bytes())
+}
+
+pub(crate) fn parse<I: Parse>(in_filename: &Path) -> anyhow::Result<I> {
+    parse_text(&read(in_filename)?).map_err(|e| parse::in_file(e, in_filename))
+}
+
+pub fn solve<P: Problem>(problem: &P, in_filename: &Path) -> anyhow::Result<Answer> {
+    solve_timed(problem, in_filename).map(|(answer, _)| answer)
+}
+
+pub fn solve_timed<P: Problem>(problem: &P, in_filename: &Path) -> anyhow::Result<(Answer, Timings)> {
+    let start = Instant::now();
+    let input = parse(in_filename)?;
+    let parsed = Instant::now();
+    let output = problem.solve(&input);
+    let timings = Timings { parse: parsed - start, solve: parsed.elapsed() };
+    Ok((output.into(), timings))
+}
@@ -0,0 +1,303 @@
+use std::any::{Any, TypeId};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use anyhow::Context;
+use crate::answer::Answer;
+use crate::artifacts;
+use crate::discover;
+use crate::log;
+use crate::manifest::{self, Params};
+use crate::parse;
+use crate::problem::{self, Problem, Timings};
+
+/// Which set of puzzle parameters a constructor should use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Profile {
+    Sample, Full
+}
+
+pub struct Run {
+    pub profile: Profile,
+    pub input: String,
+    /// Where the expected answer comes from, a `.out` file or the day's manifest.
+    pub output: String,
+    /// The expected answer when the manifest spells it out.
+    pub answer: Option<String>,
+    pub params: Params,
+    /// Too slow to be part of `run --all`; only runs when asked for explicitly.
+    pub slow: bool,
+}
+
+impl Run {
+    fn new(profile: Profile, input: &str, output: &str) -> Self {
+        Run {
+            profile,
+            input: input.to_string(),
+            output: output.to_string(),
+            answer: None,
+            params: Params::default(),
+            slow: false,
+        }
+    }
+
+    pub fn is_sample(&self) -> bool {
+        self.profile == Profile::Sample
+    }
+}
+
+pub fn dir(day: u32) -> String {
+    format!("problem{:02}", day)
+}
+
+static DATA_ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+/// Where the `problemNN` directories are: `data` in the working directory
+/// unless the command line says otherwise.
+pub fn data_root() -> &'static Path {
+    DATA_ROOT.get().map_or(Path::new("data"), PathBuf::as_path)
+}
+
+/// Only takes effect before the first run is registered.
+pub(crate) fn set_data_root(root: PathBuf) {
+    let _ = DATA_ROOT.set(root);
+}
+
+pub fn data_dir(day: u32) -> PathBuf {
+    data_root().join(dir(day))
+}
+
+/// Inputs named `sample*` get the sample profile, everything else is a full puzzle input.
+fn profile(input: &str) -> Profile {
+    if input.starts_with("sample") { Profile::Sample } else { Profile::Full }
+}
+
+/// A parsed input, shared by every part of the day that takes it.
+pub(crate) type Parsed = Arc<dyn Any + Send + Sync>;
+pub(crate) type ParseFn = Box<dyn Fn(&str) -> anyhow::Result<Parsed> + Send + Sync>;
+pub(crate) type SolveFn = Box<dyn Fn(Profile, &Params, &Parsed) -> anyhow::Result<Answer> + Send + Sync>;
+
+type TypedSolveFn<I> = Box<dyn Fn(Profile, &Params, &I) -> anyhow::Result<Answer> + Send + Sync>;
+
+pub struct Solver {
+    pub day: u32,
+    pub part: u32,
+    pub runs: Vec<Run>,
+    input_type: TypeId,
+    parse: Arc<ParseFn>,
+    solve: Arc<SolveFn>,
+}
+
+impl Solver {
+    pub fn dir(&self) -> String {
+        dir(self.day)
+    }
+
+    pub fn data_dir(&self) -> PathBuf {
+        data_dir(self.day)
+    }
+
+    /// Replaces all runs of `profile` with a single input/output pair.
+    pub fn input(&mut self, profile: Profile, input: &str, output: &str) -> &mut Self {
+        self.skip(profile);
+        self.runs.push(Run::new(profile, input, output));
+        self
+    }
+
+    pub fn skip(&mut self, profile: Profile) -> &mut Self {
+        self.runs.retain(|run| run.profile != profile);
+        self
+    }
+
+    /// Drops the run on `input`, for inputs that need different parameters.
+    pub fn exclude(&mut self, input: &str) -> &mut Self {
+        self.runs.retain(|run| run.input != input);
+        self
+    }
+
+    /// Marks the runs on full puzzle inputs as slow.
+    pub fn slow(&mut self) -> &mut Self {
+        for run in self.runs.iter_mut().filter(|run| !run.is_sample()) {
+            run.slow = true;
+        }
+        self
+    }
+
+    pub fn input_path(&self, run: &Run) -> PathBuf {
+        self.data_dir().join(&run.input)
+    }
+
+    pub fn output_path(&self, run: &Run) -> PathBuf {
+        self.data_dir().join(&run.output)
+    }
+
+    /// Whether a parse of this solver's input can be handed to `other`: same
+    /// day, same input type.
+    pub fn shares_input(&self, other: &Solver) -> bool {
+        self.day == other.day && self.input_type == other.input_type
+    }
+
+    pub fn solve(&self, run: &Run) -> anyhow::Result<Answer> {
+        self.solve_timed(run).map(|(answer, _)| answer)
+    }
+
+    pub fn solve_timed(&self, run: &Run) -> anyhow::Result<(Answer, Timings)> {
+        let (parsed, parse) = self.parse_task(run)()?;
+        let (answer, solve) = self.solve_task(run, parsed)()?;
+        Ok((answer, Timings { parse, solve }))
+    }
+
+    /// Parses the input of `run`, timed. Owns what it needs so that it can be
+    /// sent off to a thread of its own.
+    pub(crate) fn parse_task(&self, run: &Run) -> impl FnOnce() -> anyhow::Result<(Parsed, Duration)> + Send + 'static {
+        let (parse, day, path) = (self.parse.clone(), self.day, self.input_path(run));
+        move || {
+            let _log = log::enter(day);
+            let start = Instant::now();
+            let parsed = parse(&problem::read(&path)?).map_err(|e| parse::in_file(e, &path))?;
+            Ok((parsed, start.elapsed()))
+        }
+    }
+
+    /// Solves `run` on an input parsed by `parse_task` of this solver or of
+    /// one it shares its input with, timed.
+    pub(crate) fn solve_task(&self, run: &Run, parsed: Parsed) -> impl FnOnce() -> anyhow::Result<(Answer, Duration)> + Send + 'static {
+        let (solve, day, part) = (self.solve.clone(), self.day, self.part);
+        let (profile, params, input) = (run.profile, run.params.clone(), run.input.clone());
+        move || {
+            let _artifacts = artifacts::enter(artifacts::dir(day, part, &input));
+            let _log = log::enter(day);
+            let start = Instant::now();
+            let answer = solve(profile, &params, &parsed)?;
+            Ok((answer, start.elapsed()))
+        }
+    }
+
+    /// A run on an input from outside the data directory, named `input`. It
+    /// gets the parameters of this solver's first run of `profile`.
+    pub fn ad_hoc_run(&self, profile: Profile, input: &str) -> Run {
+        let params = self.runs.iter().find(|run| run.profile == profile).map(|run| run.params.clone());
+        Run { params: params.unwrap_or_default(), ..Run::new(profile, input, "") }
+    }
+
+    /// Solves `text` as the input of `run`; parse errors point into `source`.
+    pub fn solve_text(&self, run: &Run, text: &str, source: &Path) -> anyhow::Result<Answer> {
+        let parsed = {
+            let _log = log::enter(self.day);
+            (self.parse)(text).map_err(|e| parse::in_file(e, source))?
+        };
+        self.solve_task(run, parsed)().map(|(answer, _)| answer)
+    }
+
+    pub fn expected(&self, run: &Run) -> anyhow::Result<String> {
+        match &run.answer {
+            Some(answer) => Ok(answer.clone()),
+            None => Ok(fs::read_to_string(self.output_path(run))?),
+        }
+    }
+}
+
+/// Groups runs that can share one parse: runs of the same day on the same
+/// input file, by solvers taking the same input type. Groups come in the
+/// order of their first run, runs within a group keep their order.
+pub(crate) fn groups<'a>(selected: &[(&'a Solver, &'a Run)]) -> Vec<Vec<(&'a Solver, &'a Run)>> {
+    let mut groups: Vec<Vec<(&Solver, &Run)>> = Vec::new();
+    for &(solver, run) in selected {
+        let shared = groups.iter_mut().find(|group| {
+            let (first, first_run) = group[0];
+            first.shares_input(solver) && first_run.input == run.input
+        });
+        match shared {
+            Some(group) => group.push((solver, run)),
+            None => groups.push(vec![(solver, run)]),
+        }
+    }
+    groups
+}
+
+#[derive(Default)]
+pub struct Registry {
+    pub solvers: Vec<Solver>,
+    /// Problems with the data directories found while registering, e.g. a malformed manifest.
+    pub warnings: Vec<String>,
+}
+
+impl Registry {
+    /// Registers `day`/`part` to run on the inputs listed in `data/problemNN/day.toml`
+    /// or, without one, on every input that has an expected output for this
+    /// part, see `discover` for the naming rules.
+    pub fn add<P, F>(&mut self, day: u32, part: u32, build: F) -> &mut Solver
+    where
+        P: Problem + 'static,
+        P::Input: Send + Sync + 'static,
+        F: Fn(Profile) -> P + Send + Sync + 'static,
+    {
+        let runs = self.runs(day, part);
+        self.push::<P>(day, part, runs, Box::new(move |profile, _, input| Ok(Problem::solve(&build(profile), input).into())))
+    }
+
+    /// Like `add`, for problems built from the parameters in the manifest.
+    pub fn add_params<P, F>(&mut self, day: u32, part: u32, build: F) -> &mut Solver
+    where
+        P: Problem + 'static,
+        P::Input: Send + Sync + 'static,
+        F: Fn(&Params) -> anyhow::Result<P> + Send + Sync + 'static,
+    {
+        let runs = self.runs(day, part);
+        self.push::<P>(day, part, runs, Box::new(move |_, params, input| Ok(Problem::solve(&build(params)?, input).into())))
+    }
+
+    /// Registers a single run of `problem` on `input`.
+    pub fn add_run<P>(&mut self, day: u32, part: u32, input: &str, output: &str, problem: P) -> &mut Solver
+    where
+        P: Problem + Send + Sync + 'static,
+        P::Input: Send + Sync + 'static,
+    {
+        let runs = vec![Run::new(profile(input), input, output)];
+        self.push::<P>(day, part, runs, Box::new(move |_, _, input| Ok(Problem::solve(&problem, input).into())))
+    }
+
+    fn runs(&mut self, day: u32, part: u32) -> Vec<Run> {
+        let dir = data_dir(day);
+        match manifest::entries(&dir, part) {
+            Ok(Some((entries, warnings))) => {
+                warnings.into_iter().for_each(|warning| self.warn(warning));
+                entries.into_iter().map(|entry| Run {
+                    answer: entry.answer,
+                    params: entry.params,
+                    ..Run::new(profile(&entry.input), &entry.input, &entry.output)
+                }).collect()
+            }
+            Ok(None) => discover::runs(&dir, part).into_iter()
+                .map(|(input, output)| Run::new(profile(&input), &input, &output))
+                .collect(),
+            Err(e) => {
+                self.warn(format!("{:#}", e));
+                Vec::new()
+            }
+        }
+    }
+
+    /// Notes a problem with the data, once however many parts run into it.
+    fn warn(&mut self, warning: String) {
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
+    }
+
+    fn push<P>(&mut self, day: u32, part: u32, runs: Vec<Run>, solve: TypedSolveFn<P::Input>) -> &mut Solver
+    where
+        P: Problem,
+        P::Input: Send + Sync + 'static,
+    {
+        let parse: ParseFn = Box::new(|text| Ok(Arc::new(problem::parse_text::<P::Input>(text)?)));
+        let solve: SolveFn = Box::new(move |profile, params, parsed| {
+            let input = parsed.downcast_ref().context("parsed input of another type")?;
+            solve(profile, params, input)
+        });
+        let input_type = TypeId::of::<P::Input>();
+        self.solvers.push(Solver { day, part, runs, input_type, parse: Arc::new(parse), solve: Arc::new(solve) });
+        self.solvers.last_mut().unwrap()
+    }
+}
@@ -0,0 +1,142 @@
+//! `new-day`: generates the module, registration and data directory of a day.
+
+use std::fs;
+use std::path::Path;
+use anyhow::{bail, Context};
+
+use crate::manifest;
+use crate::registry;
+
+const MODULES: &str = "src/problems/mod.rs";
+
+/// How a year crate lays out the code of one day. Templates use `{day}` for
+/// the day number and `{dd}` for the same number padded to two digits.
+pub struct Template {
+    /// Contents of `src/problems/problem{dd}.rs`.
+    pub module: &'static str,
+    /// File holding the registrations; `registration` goes right before its last closing brace.
+    pub registry_file: &'static str,
+    pub registration: &'static str,
+    /// Empty expected outputs to create next to `sample.in`.
+    pub sample_outputs: &'static [&'static str],
+}
+
+impl Template {
+    fn fill(&self, text: &str, day: u32) -> String {
+        text.replace("{dd}", &format!("{:02}", day)).replace("{day}", &day.to_string())
+    }
+}
+
+/// Lists only `sample.in`, the one input `new-day` creates; a listed input
+/// without answers would be reported on every run.
+fn manifest_text(day: u32) -> String {
+    format!("# Inputs of day {}, with the parameters and expected answers they need.\n\
+        [[input]]\nfile = \"sample.in\"\n\n\
+        # Once the puzzle input and its answers are in:\n# [[input]]\n# file = \"01.in\"\n", day)
+}
+
+fn add_module(modules: &str, day: u32) -> String {
+    let line = format!("pub(crate) mod problem{:02};", day);
+    let mut lines: Vec<_> = modules.lines().collect();
+    let at = lines.iter().rposition(|l| l.starts_with("pub(crate) mod problem")).map_or(0, |i| i + 1);
+    lines.insert(at, &line);
+    lines.join("\n") + "\n"
+}
+
+fn add_registration(file: &str, registration: &str) -> anyhow::Result<String> {
+    let mut lines: Vec<_> = file.lines().collect();
+    let Some(at) = lines.iter().rposition(|l| *l == "}") else {
+        bail!("no closing brace to put the registration in front of");
+    };
+    lines.splice(at..at, registration.lines());
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Run from the year crate's directory. Refuses to touch anything if the
+/// module already exists; data files that are already there are kept.
+pub(crate) fn new_day(template: &Template, day: u32) -> anyhow::Result<()> {
+    scaffold(template, day, Path::new(""), &registry::data_dir(day))
+}
+
+/// `new-day` for the year crate in `root`, with the day's data in `data_dir`.
+fn scaffold(template: &Template, day: u32, root: &Path, data_dir: &Path) -> anyhow::Result<()> {
+    let module = root.join(format!("src/problems/problem{:02}.rs", day));
+    if module.exists() {
+        bail!("{} already exists", module.display());
+    }
+    let modules_file = root.join(MODULES);
+    let modules = fs::read_to_string(&modules_file)
+        .with_context(|| format!("can't read {}, run new-day from the year's crate directory", modules_file.display()))?;
+    let modules = add_module(&modules, day);
+    // The registrations may well live in the same file as the module list.
+    let registry_file = root.join(template.registry_file);
+    let registrations = match template.registry_file {
+        MODULES => modules.clone(),
+        _ => fs::read_to_string(&registry_file).with_context(|| format!("can't read {}", registry_file.display()))?,
+    };
+    let registrations = add_registration(&registrations, &template.fill(template.registration, day))
+        .with_context(|| format!("can't register day {} in {}", day, registry_file.display()))?;
+
+    fs::write(&module, template.fill(template.module, day))?;
+    println!("Created {}", module.display());
+    if template.registry_file != MODULES {
+        fs::write(&modules_file, modules)?;
+        println!("Updated {}", modules_file.display());
+    }
+    fs::write(&registry_file, registrations)?;
+    println!("Updated {}", registry_file.display());
+
+    fs::create_dir_all(data_dir)?;
+    let files = ["sample.in"].iter().chain(template.sample_outputs).map(|file| (file.to_string(), String::new()))
+        .chain([(manifest::FILE_NAME.to_string(), manifest_text(day))]);
+    for (file, contents) in files {
+        let path = data_dir.join(file);
+        if !path.exists() {
+            fs::write(&path, contents)?;
+            println!("Created {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scaffold::*;
+
+    #[test]
+    fn module_goes_after_the_last_day() {
+        let modules = "pub(crate) mod problem00;\npub(crate) mod common;\npub(crate) mod problem01;\n\npub fn register_all() {}\n";
+        assert_eq!(add_module(modules, 2),
+            "pub(crate) mod problem00;\npub(crate) mod common;\npub(crate) mod problem01;\npub(crate) mod problem02;\n\npub fn register_all() {}\n");
+    }
+
+    #[test]
+    fn registration_goes_before_the_last_brace() {
+        let file = "fn register_all() {\n    a();\n}\n";
+        assert_eq!(add_registration(file, "    b();").unwrap(), "fn register_all() {\n    a();\n    b();\n}\n");
+        assert!(add_registration("", "    b();").is_err());
+    }
+
+    #[test]
+    fn a_new_day_registers_without_warnings() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-test-{}", std::process::id()));
+        fs::create_dir_all(root.join("src/problems")).unwrap();
+        fs::write(root.join(MODULES), "pub(crate) mod problem01;\n\npub fn register_all() {\n}\n").unwrap();
+        let template = Template {
+            module: "pub struct Problem{dd};\n",
+            registry_file: MODULES,
+            registration: "    register({day});",
+            sample_outputs: &["sample-part1.out", "sample-part2.out"],
+        };
+        let data_dir = root.join("data/problem02");
+        scaffold(&template, 2, &root, &data_dir).unwrap();
+        assert!(fs::read_to_string(root.join(MODULES)).unwrap().contains("pub(crate) mod problem02;"));
+        for part in [1, 2] {
+            let (entries, warnings) = manifest::entries(&data_dir, part).unwrap().unwrap();
+            assert_eq!(entries.iter().map(|e| e.input.as_str()).collect::<Vec<_>>(), vec!["sample.in"]);
+            assert!(warnings.is_empty(), "{:?}", warnings);
+        }
+        assert!(scaffold(&template, 2, &root, &data_dir).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,264 @@
+//! Shortest paths over graphs given by a successor function.
+//!
+//! Nodes are whatever a solver finds convenient: grid positions, positions
+//! with a facing, whole machine states. A search starts from any number of
+//! nodes at once, so searching backwards from several targets is a search
+//! from all of them with a successor function that walks edges in reverse.
+//!
+//! Every search returns `Paths`, which remembers for each node it reached the
+//! cost of getting there and all the nodes it can be reached from at that
+//! cost. That's enough to rebuild a shortest path, or to find every node that
+//! lies on some shortest path.
+
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// What a search found out about the nodes it reached.
+pub struct Paths<N, C> {
+    index: HashMap<N, usize>,
+    nodes: Vec<N>,
+    costs: Vec<C>,
+    predecessors: Vec<Vec<usize>>,
+    reached: Option<usize>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy + Ord> Paths<N, C> {
+    fn new() -> Self {
+        Paths { index: HashMap::new(), nodes: Vec::new(), costs: Vec::new(), predecessors: Vec::new(), reached: None }
+    }
+
+    /// Adds a start node, unless it's already there.
+    fn start(&mut self, node: N, zero: C) -> Option<usize> {
+        if self.index.contains_key(&node) {
+            return None;
+        }
+        Some(self.push(node, zero, None))
+    }
+
+    fn push(&mut self, node: N, cost: C, from: Option<usize>) -> usize {
+        let i = self.nodes.len();
+        self.index.insert(node.clone(), i);
+        self.nodes.push(node);
+        self.costs.push(cost);
+        self.predecessors.push(from.into_iter().collect());
+        i
+    }
+
+    /// Notes that `node` can be reached from node `from` at `cost`. Returns
+    /// the node's index if that's the best way to it found so far, which
+    /// means it has to be expanded (again).
+    fn relax(&mut self, node: N, cost: C, from: usize) -> Option<usize> {
+        let Some(&i) = self.index.get(&node) else {
+            return Some(self.push(node, cost, Some(from)));
+        };
+        if cost < self.costs[i] {
+            self.costs[i] = cost;
+            self.predecessors[i] = vec![from];
+            Some(i)
+        } else {
+            if cost == self.costs[i] && !self.predecessors[i].contains(&from) {
+                self.predecessors[i].push(from);
+            }
+            None
+        }
+    }
+
+    /// The cost of the cheapest path to `node`, if it was reached.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.index.get(node).map(|&i| self.costs[i])
+    }
+
+    /// The goal the search stopped at, with its cost. `None` when the search
+    /// had no goal or ran out of nodes before finding one.
+    pub fn reached(&self) -> Option<(&N, C)> {
+        self.reached.map(|i| (&self.nodes[i], self.costs[i]))
+    }
+
+    /// Every reached node with its cost, in the order they were first seen.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> + '_ {
+        self.nodes.iter().zip(self.costs.iter().copied())
+    }
+
+    /// The nodes `node` is reached from on its cheapest paths. Start nodes
+    /// have none.
+    pub fn predecessors(&self, node: &N) -> impl Iterator<Item = &N> + '_ {
+        let from = self.index.get(node).map_or(&[][..], |&i| &self.predecessors[i]);
+        from.iter().map(|&i| &self.nodes[i])
+    }
+
+    /// One cheapest path from a start node to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        let mut i = *self.index.get(node)?;
+        let mut path = vec![self.nodes[i].clone()];
+        while let Some(&from) = self.predecessors[i].first() {
+            i = from;
+            path.push(self.nodes[i].clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every node on some cheapest path to one of `targets`, targets
+    /// included, each once. Targets that weren't reached are skipped.
+    pub fn on_paths_to(&self, targets: impl IntoIterator<Item = impl Borrow<N>>) -> Vec<&N> {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut stack: Vec<usize> = targets.into_iter().filter_map(|node| self.index.get(node.borrow()).copied()).collect();
+        let mut found = Vec::new();
+        while let Some(i) = stack.pop() {
+            if std::mem::replace(&mut seen[i], true) {
+                continue;
+            }
+            found.push(&self.nodes[i]);
+            stack.extend(&self.predecessors[i]);
+        }
+        found
+    }
+}
+
+/// Breadth-first search where every edge costs 1, exploring everything
+/// reachable from `starts`.
+pub fn bfs<N, I>(starts: impl IntoIterator<Item = N>, next: impl FnMut(&N) -> I) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs_to(starts, next, |_| false)
+}
+
+/// Like `bfs`, but stops at the first node `goal` holds for.
+pub fn bfs_to<N, I>(starts: impl IntoIterator<Item = N>, mut next: impl FnMut(&N) -> I, mut goal: impl FnMut(&N) -> bool) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new();
+    let mut queue: VecDeque<usize> = starts.into_iter().filter_map(|start| paths.start(start, 0)).collect();
+    while let Some(i) = queue.pop_front() {
+        let node = paths.nodes[i].clone();
+        if goal(&node) {
+            paths.reached = Some(i);
+            break;
+        }
+        let cost = paths.costs[i] + 1;
+        for succ in next(&node) {
+            queue.extend(paths.relax(succ, cost, i));
+        }
+    }
+    paths
+}
+
+/// Dijkstra's algorithm over edges `next` lists with their costs, which must
+/// not be negative. Explores everything reachable from `starts`.
+pub fn dijkstra<N, C, I>(starts: impl IntoIterator<Item = N>, next: impl FnMut(&N) -> I) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, next, |_| C::default(), |_| false)
+}
+
+/// Like `dijkstra`, but stops once the cheapest node `goal` holds for is
+/// known.
+pub fn dijkstra_to<N, C, I>(starts: impl IntoIterator<Item = N>, next: impl FnMut(&N) -> I, goal: impl FnMut(&N) -> bool) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, next, |_| C::default(), goal)
+}
+
+/// A* search towards the nodes `goal` holds for. `heuristic` guesses the
+/// remaining cost from a node and must never overestimate it, nor drop by
+/// more than an edge's cost along that edge; otherwise the path found may
+/// not be the cheapest. Nodes with a tied cost the search didn't need to
+/// expand may be missing from the goal's predecessors.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut next: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let zero = C::default();
+    let mut paths = Paths::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if let Some(i) = paths.start(start, zero) {
+            queue.push(Reverse((heuristic(&paths.nodes[i]), zero, i)));
+        }
+    }
+    while let Some(Reverse((_, cost, i))) = queue.pop() {
+        if cost > paths.costs[i] {
+            continue;
+        }
+        let node = paths.nodes[i].clone();
+        if goal(&node) {
+            paths.reached = Some(i);
+            break;
+        }
+        for (succ, edge) in next(&node) {
+            let cost = cost + edge;
+            if let Some(j) = paths.relax(succ, cost, i) {
+                queue.push(Reverse((cost + heuristic(&paths.nodes[j]), cost, j)));
+            }
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::search::*;
+    use crate::grid::Grid;
+
+    fn maze() -> Grid<bool> {
+        Grid::parse("...#\n.#..\n...#\n", "one of .#", |c| Some(c == '.')).unwrap()
+    }
+
+    #[test]
+    fn bfs_finds_distances_and_paths() {
+        let grid = maze();
+        let open = |&pos: &(usize, usize)| grid.neighbours4(pos).filter(|&pos| grid[pos]).collect::<Vec<_>>();
+        let paths = bfs([(0, 0)], open);
+        assert_eq!(paths.cost(&(1, 3)), Some(4));
+        assert_eq!(paths.cost(&(0, 3)), None);
+        assert_eq!(paths.iter().count(), 9);
+        let path = paths.path_to(&(2, 2)).unwrap();
+        assert_eq!((path.first(), path.last(), path.len()), (Some(&(0, 0)), Some(&(2, 2)), 5));
+        // Both ways around the wall are equally short.
+        assert_eq!(paths.predecessors(&(2, 1)).count(), 1);
+        assert_eq!(paths.predecessors(&(2, 2)).count(), 2);
+        assert_eq!(paths.on_paths_to([(2, 2)]).len(), 8);
+
+        let paths = bfs_to([(0, 0), (2, 2)], open, |&pos| pos == (1, 3));
+        assert_eq!(paths.reached(), Some((&(1, 3), 2)));
+    }
+
+    #[test]
+    fn weighted_searches_agree() {
+        // Going down costs 10, every other step costs 1.
+        let grid = maze();
+        let next = |&pos: &(usize, usize)| {
+            grid.neighbours4(pos).filter(|&next| grid[next]).map(move |next| (next, if next.0 > pos.0 { 10 } else { 1 })).collect::<Vec<_>>()
+        };
+        let goal = |&pos: &(usize, usize)| pos == (2, 2);
+        let all = dijkstra([(0, 0)], next);
+        assert_eq!(all.cost(&(2, 2)), Some(22));
+        assert_eq!(all.cost(&(0, 0)), Some(0));
+        assert_eq!(dijkstra_to([(0, 0)], next, goal).reached(), Some((&(2, 2), 22)));
+        let manhattan = |&(row, col): &(usize, usize)| row.abs_diff(2) + col.abs_diff(2);
+        let found = astar([(0, 0)], next, manhattan, goal);
+        assert_eq!(found.reached(), Some((&(2, 2), 22)));
+        assert_eq!(found.path_to(&(2, 2)).unwrap().len(), 5);
+        assert!(found.iter().count() <= all.iter().count());
+    }
+}
@@ -0,0 +1,41 @@
+//! Turns a registry into `cargo test` cases, one per registered run.
+
+use std::process::ExitCode;
+use std::sync::Arc;
+use libtest_mimic::{Arguments, Failed, Trial};
+
+use crate::registry::{Registry, Run, Solver};
+use crate::verify;
+
+/// Entry point for a `harness = false` test target. Each run becomes a test
+/// named like `problem01::part2::sample.in`; runs on full puzzle inputs are
+/// ignored by default, `cargo test -- --include-ignored` checks them too.
+pub fn main(registry: Registry) -> ExitCode {
+    let args = Arguments::from_args();
+    let registry = Arc::new(registry);
+    let mut trials: Vec<_> = registry.warnings.iter().enumerate().map(|(i, warning)| {
+        let warning = warning.clone();
+        Trial::test(format!("data::warning{}", i), move || Err(warning.into()))
+    }).collect();
+    for (i, solver) in registry.solvers.iter().enumerate() {
+        for (j, run) in solver.runs.iter().enumerate() {
+            let name = format!("{}::part{}::{}", solver.dir(), solver.part, run.input);
+            let registry = registry.clone();
+            let trial = Trial::test(name, move || {
+                let solver = &registry.solvers[i];
+                check(solver, &solver.runs[j])
+            });
+            trials.push(trial.with_ignored_flag(!run.is_sample() || run.slow));
+        }
+    }
+    libtest_mimic::run(&args, trials).exit_code()
+}
+
+fn check(solver: &Solver, run: &Run) -> Result<(), Failed> {
+    let actual = solver.solve(run)?;
+    let expected = solver.expected(run).map_err(|e| format!("can't read {}: {:#}", run.output, e))?;
+    if !actual.matches(&expected) {
+        return Err(format!("answer differs from {}\n{}", run.output, verify::diff(&expected, &actual.to_string())).into());
+    }
+    Ok(())
+}
@@ -0,0 +1,40 @@
+
+/// Line-by-line diff of two answers, `-` for expected and `+` for actual.
+pub(crate) fn diff(expected: &str, actual: &str) -> String {
+    const MAX_LINES: usize = 20;
+    let expected: Vec<_> = expected.trim_end().lines().collect();
+    let actual: Vec<_> = actual.trim_end().lines().collect();
+    let mut out = Vec::new();
+    let mut differing = 0;
+    for i in 0..expected.len().max(actual.len()) {
+        let (e, a) = (expected.get(i), actual.get(i));
+        if e == a {
+            continue;
+        }
+        differing += 1;
+        if differing > MAX_LINES {
+            continue;
+        }
+        if let Some(e) = e {
+            out.push(format!("  {:>4} - {}", i + 1, e));
+        }
+        if let Some(a) = a {
+            out.push(format!("  {:>4} + {}", i + 1, a));
+        }
+    }
+    if differing > MAX_LINES {
+        out.push(format!("  ... {} more differing lines", differing - MAX_LINES));
+    }
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::verify::*;
+
+    #[test]
+    fn diff_shows_only_changed_lines() {
+        let d = diff("a\nb\nc\n", "a\nx\nc\nd\n");
+        assert_eq!(d, "     2 - b\n     2 + x\n     4 + d");
+    }
+}
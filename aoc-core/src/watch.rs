@@ -0,0 +1,88 @@
+//! `watch`: re-verifies one day whenever its module or its data change.
+//!
+//! A running binary can't pick up edits to its own code, so every round goes
+//! through `cargo run`, which rebuilds first when the sources changed.
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+use anyhow::{bail, Context};
+use notify::{RecursiveMode, Watcher};
+
+use crate::registry;
+
+/// Editors tend to save in several steps; changes this close together trigger one round.
+const SETTLE: Duration = Duration::from_millis(200);
+
+/// `verify --day N` of the current binary, rebuilt by cargo if needed, with
+/// the `global` flags this one got.
+fn verify_command(day: u32, timeout: u64, jobs: u32, global: &[String]) -> anyhow::Result<Command> {
+    let exe = env::current_exe()?;
+    let Some(bin) = exe.file_stem().and_then(|stem| stem.to_str()) else {
+        bail!("can't tell the binary's name from {}", exe.display());
+    };
+    let mut command = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    command.args(["run", "--quiet", "--bin", bin]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    command.arg("--").args(global);
+    command.args(["verify", "--day", &day.to_string(), "--timeout", &timeout.to_string(), "--jobs", &jobs.to_string()]);
+    Ok(command)
+}
+
+fn verify(command: &mut Command) -> anyhow::Result<()> {
+    println!("---- {}", "-".repeat(60));
+    let status = command.status().context("can't start cargo")?;
+    match status.code() {
+        Some(0) => println!("---- all passed, watching for changes"),
+        _ => println!("---- {}, watching for changes", status),
+    }
+    Ok(())
+}
+
+/// Run from the year crate's directory. Only returns on errors.
+pub(crate) fn watch(day: u32, timeout: u64, jobs: u32, global: &[String]) -> anyhow::Result<()> {
+    let module = PathBuf::from(format!("src/problems/problem{:02}.rs", day));
+    let data_dir = registry::data_dir(day);
+    if !module.exists() {
+        bail!("{} doesn't exist, run watch from the year's crate directory", module.display());
+    }
+    // The watcher reports absolute paths.
+    let module_abs = module.canonicalize()?;
+    let data_abs = data_dir.canonicalize().unwrap_or_else(|_| env::current_dir().unwrap_or_default().join(&data_dir));
+    // Editors often replace a file instead of writing to it, which a watch on
+    // the file itself would lose track of, so the whole directory is watched.
+    let modules = module.parent().unwrap_or(Path::new("."));
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.watch(modules, RecursiveMode::NonRecursive)
+        .with_context(|| format!("can't watch {}", modules.display()))?;
+    if data_dir.is_dir() {
+        watcher.watch(&data_dir, RecursiveMode::Recursive)
+            .with_context(|| format!("can't watch {}", data_dir.display()))?;
+    }
+    println!("Watching {} and {}", module.display(), data_dir.display());
+
+    let mut command = verify_command(day, timeout, jobs, global)?;
+    let relevant = |event: &notify::Event| event.kind.is_modify() || event.kind.is_create() || event.kind.is_remove();
+    let affects_day = |path: &PathBuf| *path == module_abs || path.starts_with(&data_abs);
+    verify(&mut command)?;
+    loop {
+        let event = rx.recv().context("file watcher stopped")??;
+        if !relevant(&event) || !event.paths.iter().any(affects_day) {
+            continue;
+        }
+        // Let the rest of the save land before rebuilding.
+        loop {
+            match rx.recv_timeout(SETTLE) {
+                Ok(event) => { event?; }
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => bail!("file watcher stopped"),
+            }
+        }
+        verify(&mut command)?;
+    }
+}
@@ -0,0 +1,4 @@
+mod problems;
+mod registry;
+
+pub use registry::register_all;
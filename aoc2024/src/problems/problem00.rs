@@ -1,5 +1,5 @@
-use std::io::{BufRead, Write};
-use crate::problems::common::{Readable, Solvable};
+use std::io::BufRead;
+use crate::problems::common::{Parse, Problem};
 use anyhow::Result;
 
 #[derive(Debug, PartialEq)]
@@ -8,7 +8,7 @@ pub(crate) struct Input {
     b: i32,
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Input>
     {
         let mut s = String::new();
@@ -22,34 +22,34 @@ impl Readable for Input {
 
 type Output = i32;
 
-pub(crate) struct Problem {}
+pub(crate) struct PartOne {}
 
-impl Problem {
-    fn solve(&self, input: Input) -> Output {
+impl PartOne {
+    fn solve(&self, input: &Input) -> Output {
         input.a + input.b
     }
 }
 
-impl Solvable for Problem {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use std::io::BufReader;
-    use crate::problems::common::Readable;
-    use crate::problems::problem00::{Input, Problem};
+    use crate::problems::common::Parse;
+    use crate::problems::problem00::{Input, PartOne};
 
     #[test]
     fn execute_test() {
         let input = Input { a: 1, b: 2 };
-        let problem = Problem {};
-        assert_eq!(problem.solve(input), 3);
+        let problem = PartOne {};
+        assert_eq!(problem.solve(&input), 3);
     }
 
     #[test]
@@ -1,14 +1,15 @@
 use std::collections::HashMap;
-use std::io::{BufRead, Write};
-use crate::problems::common::{Readable, Solvable};
+use std::io::BufRead;
+use crate::problems::common::{Parse, Problem};
 use anyhow::Result;
 
-struct Input {
+#[derive(Clone)]
+pub(crate) struct Input {
     left: Vec<i32>,
     right: Vec<i32>,
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> Result<Self>
     where
         Self: Sized
@@ -28,9 +29,9 @@ impl Readable for Input {
 
 type Output = i32;
 
-pub(crate) struct Problem {}
+pub(crate) struct PartOne {}
 
-impl Problem {
+impl PartOne {
     fn solve(&self, mut input: Input) -> Output {
         input.left.sort();
         input.right.sort();
@@ -38,12 +39,12 @@ impl Problem {
     }
 }
 
-impl Solvable for Problem {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input.clone())
     }
 }
 
@@ -59,11 +60,11 @@ impl PartTwo {
     }
 }
 
-impl Solvable for PartTwo {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartTwo {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input.clone())
     }
 }
\ No newline at end of file
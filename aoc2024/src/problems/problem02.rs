@@ -1,12 +1,11 @@
-use std::io::{BufRead, Write};
-use crate::problems::common::{Readable, Solvable};
-use anyhow::Result;
+use std::io::BufRead;
+use crate::problems::common::{Parse, Problem};
 
-struct Input {
+pub(crate) struct Input {
     reports: Vec<Vec<i32>>,
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
@@ -47,12 +46,12 @@ impl PartOne {
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(&input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input)
     }
 }
 
@@ -89,11 +88,11 @@ impl PartTwo {
     }
 }
 
-impl Solvable for PartTwo {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(&input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartTwo {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input)
     }
 }
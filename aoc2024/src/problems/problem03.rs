@@ -1,5 +1,5 @@
-use std::io::{BufRead, Write};
-use crate::problems::common::{Readable, Solvable};
+use std::io::BufRead;
+use crate::problems::common::{Parse, ParseError, Problem};
 use nom::{IResult, Parser};
 use nom::branch::alt;
 use nom::bytes::{tag, take, take_while_m_n};
@@ -12,7 +12,7 @@ struct Mul {
     lhs: i32,
     rhs: i32,
 }
-struct Input {
+pub(crate) struct Input {
     muls: Vec<Mul>,
 }
 
@@ -35,7 +35,7 @@ fn mul(input: &str) -> IResult<&str, Mul> {
 }
 
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
@@ -46,7 +46,7 @@ impl Readable for Input {
                 mul.map(Some),
                 take(1usize).map(|_| None),
             )), eof);
-        let (_, (x, _)) = parser.parse(&*s).map_err(|e| anyhow::anyhow!("lol {}", e))?;
+        let (_, (x, _)) = parser.parse(&*s).map_err(|e| ParseError::from_nom(&s, e))?;
         let y = x.into_iter().filter_map(|x| x).collect();
         Ok(Input { muls: y })
     }
@@ -63,12 +63,12 @@ impl PartOne {
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(&input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input)
     }
 }
 
@@ -80,11 +80,11 @@ enum Instruction {
     Dont,
 }
 
-struct InputPartTwo {
+pub(crate) struct InputPartTwo {
     instructions: Vec<Instruction>,
 }
 
-impl Readable for InputPartTwo {
+impl Parse for InputPartTwo {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
@@ -97,7 +97,7 @@ impl Readable for InputPartTwo {
             tag("don't()").map(|_| Some(Instruction::Dont)),
             take(1usize).map(|_| None),
         )), eof);
-        let (_, (x, _)) = parser.parse(&*s).map_err(|e| anyhow::anyhow!("lol {}", e))?;
+        let (_, (x, _)) = parser.parse(&*s).map_err(|e| ParseError::from_nom(&s, e))?;
         let y = x.into_iter().filter_map(|x| x).collect();
         Ok(InputPartTwo { instructions: y })
     }
@@ -125,12 +125,12 @@ impl PartTwo {
     }
 }
 
-impl Solvable for PartTwo {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = InputPartTwo::parse_from(input)?;
-        let out = self.solve(&input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartTwo {
+    type Input = InputPartTwo;
+    type Output = Output;
+
+    fn solve(&self, input: &InputPartTwo) -> Output {
+        self.solve(input)
     }
 }
 #[cfg(test)]
@@ -1,42 +1,29 @@
-use std::io::{BufRead, Write};
-use crate::problems::common::{Readable, Solvable};
+use std::io::BufRead;
+use crate::problems::common::{Grid, Parse, Problem};
 
-struct Input {
-    field: Vec<Vec<char>>,
+#[derive(Clone)]
+pub(crate) struct Input {
+    field: Grid<char>,
 }
 
 impl Input {
     fn rotate(&self) -> Input {
-        let (rows, cols) = (self.field.len(), self.field[0].len());
-        let field2 = (0..cols).map(
-            |col| {
-                (0..rows).map(|row| self.field[rows - row - 1][col]).collect()
-            }
-        ).collect();
-        Input { field: field2 }
+        Input { field: self.field.rotate() }
     }
 
     fn mirror(&self) -> Input {
-        let (rows, cols) = (self.field.len(), self.field[0].len());
-        let field2 = (0..rows).map(
-            |row| {
-                (0..cols).map(|col| self.field[row][cols - col - 1]).collect()
-            }
-        ).collect();
-        Input { field: field2 }
+        Input { field: self.field.mirror() }
     }
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
     {
         let mut s = String::new();
         input.read_to_string(&mut s)?;
-        let field = s.lines()
-            .filter(|s| !s.is_empty())
-            .map(|s| s.chars().collect()).collect();
+        let field = Grid::parse_chars(&s)?;
         Ok(Input { field })
     }
 }
@@ -47,12 +34,12 @@ type Output = i32;
 
 impl PartOne {
     fn count_horizontal(input: &Input, s: &Vec<char>) -> i32 {
-        let (rows, cols) = (input.field.len(), input.field[0].len());
+        let (rows, cols) = (input.field.rows(), input.field.cols());
         (0..rows).map(
             |row| {
                 (0..cols - s.len() + 1).map(|col| {
                     let is_match = (0..s.len()).all(|i| {
-                        input.field[row][col + i] == s[i]
+                        input.field[(row, col + i)] == s[i]
                     });
                     if is_match { 1 } else { 0 }
                 }).sum::<i32>()
@@ -61,12 +48,12 @@ impl PartOne {
     }
 
     fn count_diagonal(input: &Input, s: &Vec<char>) -> i32 {
-        let (rows, cols) = (input.field.len(), input.field[0].len());
+        let (rows, cols) = (input.field.rows(), input.field.cols());
         (0..rows - s.len() + 1).map(
             |row| {
                 (0..cols - s.len() + 1).map(|col| {
                     let is_match = (0..s.len()).all(|i| {
-                        input.field[row + i][col + i] == s[i]
+                        input.field[(row + i, col + i)] == s[i]
                     });
                     if is_match { 1 } else { 0 }
                 }).sum::<i32>()
@@ -86,12 +73,12 @@ impl PartOne {
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input.clone())
     }
 }
 
@@ -100,14 +87,14 @@ pub(crate) struct PartTwo {}
 impl PartTwo {
 
     fn count_match(input: &Input, s: &Vec<Vec<char>>) -> i32 {
-        let (rows, cols) = (input.field.len(), input.field[0].len());
+        let (rows, cols) = (input.field.rows(), input.field.cols());
         let (n, m) = (s.len(), s[0].len());
         (0..rows - n + 1).map(
             |row| {
                 (0..cols - n + 1).map(|col| {
                     let is_match = (0..n).all(|i| {
                         (0..m).all(|j| {
-                            s[i][j] == '.' || input.field[row + i][col + j] == s[i][j]
+                            s[i][j] == '.' || input.field[(row + i, col + j)] == s[i][j]
                         })
                     });
                     if is_match { 1 } else { 0 }
@@ -131,12 +118,12 @@ impl PartTwo {
     }
 }
 
-impl Solvable for PartTwo {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartTwo {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input.clone())
     }
 }
 #[cfg(test)]
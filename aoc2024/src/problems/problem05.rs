@@ -1,71 +1,51 @@
 use std::cmp::Ordering;
 use std::collections::HashSet;
-use std::io::{BufRead, Write};
+use std::io::BufRead;
 use nom::Parser;
-use nom::bytes::take_while;
 use nom::character::char;
-use nom::character::complete::{line_ending, newline};
-use nom::combinator::{eof, map_res};
+use nom::character::complete::line_ending;
 use nom::IResult;
-use nom::multi::{many1, separated_list1};
-use crate::problems::common::{Readable, Solvable};
+use crate::problems::common::{Parse, Problem};
+use crate::problems::common::parse::{comma_separated, lines, parse_all, unsigned};
 
+#[derive(Clone)]
 struct Order {
     before: i32,
     after: i32,
 }
 
-struct Input {
+#[derive(Clone)]
+pub(crate) struct Input {
     limits: Vec<Order>,
     updates: Vec<Vec<i32>>,
 }
 
-fn number(input: &str) -> IResult<&str, i32> {
-    fn is_digit(c: char) -> bool {
-        c.is_digit(10)
-    }
-    map_res(
-        take_while(is_digit),
-        str::parse
-    ).parse(input)
-}
-
 fn order(input: &str) -> IResult<&str, Order> {
-    let (input, (before, _, after, _)) = (
-        number,
+    let (input, (before, _, after)) = (
+        unsigned,
         char('|'),
-        number,
-        line_ending,
+        unsigned,
     ).parse(input)?;
     Ok((input, Order { before, after }))
 }
 
-fn update(input: &str) -> IResult<&str, Vec<i32>> {
-    let (input, (v, _)) = (
-        separated_list1(char(','), number),
-        line_ending
-    ).parse(input)?;
-    Ok((input, v))
-}
-
 fn parse_input(input: &str) -> IResult<&str, Input> {
     let (input, (limits, _, updates)) = (
-        many1(order),
+        lines(order),
         line_ending,
-        many1(update),
+        lines(comma_separated(unsigned)),
     ).parse(input)?;
     Ok((input, Input {limits, updates }))
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
     {
         let mut s = String::new();
         input.read_to_string(&mut s)?;
-        let (_, r) = parse_input(&*s).map_err(|e| anyhow::anyhow!("lol {}", e))?;
-        Ok(r)
+        Ok(parse_all(&s, parse_input)?)
     }
 }
 
@@ -91,12 +71,12 @@ impl PartOne {
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input.clone())
     }
 }
 
@@ -129,11 +109,11 @@ impl PartTwo {
     }
 }
 
-impl Solvable for PartTwo {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartTwo {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input.clone())
     }
 }
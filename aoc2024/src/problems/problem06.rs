@@ -1,23 +1,8 @@
 use std::collections::{HashMap, HashSet};
-use std::io::{BufRead, Write};
+use std::io::BufRead;
 use anyhow::anyhow;
-use crate::problems::common::{Readable, Solvable};
-
-#[derive(Copy, Clone)]
-enum Direction {
-    Left, Up, Right, Down,
-}
-
-impl Direction {
-    fn rotate(&self) -> Self {
-        match self {
-            Direction::Left => Direction::Up,
-            Direction::Up => Direction::Right,
-            Direction::Right => Direction::Down,
-            Direction::Down => Direction::Left,
-        }
-    }
-}
+use crate::problems::common::{Grid, Parse, Problem};
+use crate::problems::common::geometry::Direction;
 
 #[derive(Copy, Clone)]
 enum Cell {
@@ -26,67 +11,44 @@ enum Cell {
 
 #[derive(Copy, Clone)]
 struct Pos {
-    row: i32,
-    col: i32,
+    row: usize,
+    col: usize,
     dir: Direction,
 }
 
 impl Pos {
-    fn step(&self) -> Self {
-        match &self.dir {
-            Direction::Left => Pos {row: self.row, col: self.col - 1, dir: self.dir},
-            Direction::Up => Pos {row: self.row - 1, col: self.col, dir: self.dir},
-            Direction::Right => Pos {row: self.row, col: self.col + 1, dir: self.dir},
-            Direction::Down => Pos {row: self.row + 1, col: self.col, dir: self.dir},
-        }
+    /// The guard one step ahead, unless that leaves the map.
+    fn step(&self, field: &Grid<Cell>) -> Option<Self> {
+        let (row, col) = field.offset((self.row, self.col), self.dir.delta())?;
+        Some(Pos {row, col, dir: self.dir})
     }
 }
 
-struct Input {
-    field: Vec<Vec<Cell>>,
+#[derive(Clone)]
+pub(crate) struct Input {
+    field: Grid<Cell>,
     guard: Pos,
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
     {
         let mut s = String::new();
         input.read_to_string(&mut s)?;
-        let field = s.lines().filter(|s| !s.is_empty()).map(|s| {
-            s.chars()
-        });
-        let mut pos: Option<Pos> = None;
-        let field = (0..).zip(field).map(|(i, row) | {
-            (0..).zip(row).map(|(j, cell)| {
-                match cell {
-                    '#' => Cell::Crate,
-                    '.' => Cell::Empty,
-                    'v' => {
-                        pos = Some(Pos {row: i, col: j, dir: Direction::Down});
-                        Cell::Empty
-                    },
-                    '>' => {
-                        pos = Some(Pos {row: i, col: j, dir: Direction::Right});
-                        Cell::Empty
-                    },
-                    '^' => {
-                        pos = Some(Pos {row: i, col: j, dir: Direction::Up});
-                        Cell::Empty
-                    },
-                    '<' => {
-                        pos = Some(Pos {row: i, col: j, dir: Direction::Left});
-                        Cell::Empty
-                    },
-                    _ => todo!(),
-                }
-            }).collect()
-        }).collect();
-        Ok(Input {
-            field,
-            guard: pos.ok_or_else(|| anyhow!("No guard"))?,
-        })
+        let (field, markers) = Grid::parse_marked(&s, "one of #.v>^<", "v>^<", |c| {
+            match c {
+                '#' => Some(Cell::Crate),
+                '.' | 'v' | '>' | '^' | '<' => Some(Cell::Empty),
+                _ => None,
+            }
+        })?;
+        let guard = "v>^<".chars().find_map(|c| {
+            let (row, col) = markers.all(c).next()?;
+            Some(Pos {row, col, dir: Direction::from_arrow(c)?})
+        }).ok_or_else(|| anyhow!("No guard"))?;
+        Ok(Input { field, guard })
     }
 }
 
@@ -98,18 +60,13 @@ impl PartOne {
     fn solve(&self, input: Input) -> Output {
         let mut visited = HashSet::new();
         let mut guard = input.guard;
-        let inside = |guard: &Pos| -> bool {
-            0 <= guard.row && guard.row < input.field.len() as i32
-                && 0 <= guard.col && guard.col < input.field[0].len() as i32
-        };
         loop {
             visited.insert((guard.row, guard.col));
-            let next = guard.step();
-            if !inside(&next) {
+            let Some(next) = guard.step(&input.field) else {
                 break;
-            }
-            if let Cell::Crate = input.field[next.row as usize][next.col as usize] {
-                guard = Pos {row: guard.row, col: guard.col, dir: guard.dir.rotate()};
+            };
+            if let Cell::Crate = input.field[(next.row, next.col)] {
+                guard = Pos {row: guard.row, col: guard.col, dir: guard.dir.clockwise()};
             } else {
                 guard = next
             }
@@ -118,12 +75,12 @@ impl PartOne {
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input.clone())
     }
 }
 
@@ -134,22 +91,17 @@ impl PartTwo {
     fn is_loop(input: Input) -> bool {
         let mut visited = HashMap::new();
         let mut guard = input.guard;
-        let inside = |guard: &Pos| -> bool {
-            0 <= guard.row && guard.row < input.field.len() as i32
-                && 0 <= guard.col && guard.col < input.field[0].len() as i32
-        };
         loop {
             let count = visited.entry((guard.row, guard.col)).or_insert(0);
             *count += 1;
             if *count > 4 {
                 return true;
             }
-            let next = guard.step();
-            if !inside(&next) {
+            let Some(next) = guard.step(&input.field) else {
                 return false;
-            }
-            if let Cell::Crate = input.field[next.row as usize][next.col as usize] {
-                guard = Pos {row: guard.row, col: guard.col, dir: guard.dir.rotate()};
+            };
+            if let Cell::Crate = input.field[(next.row, next.col)] {
+                guard = Pos {row: guard.row, col: guard.col, dir: guard.dir.clockwise()};
             } else {
                 guard = next
             }
@@ -158,29 +110,27 @@ impl PartTwo {
     fn solve(&self, input: Input) -> Output {
         let mut field = input.field;
         let mut count = 0;
-        for i in 0..field.len() {
-            for j in 0..field[0].len() {
-                if (i, j) == (input.guard.row as usize, input.guard.col as usize) {
-                    continue
-                }
-                if let Cell::Crate = field[i][j] {
-                    continue
-                }
-                field[i][j] = Cell::Crate;
-                if Self::is_loop(Input{field: field.clone(), guard: input.guard}) {
-                    count += 1;
-                }
-                field[i][j] = Cell::Empty;
+        for pos in field.positions() {
+            if pos == (input.guard.row, input.guard.col) {
+                continue
             }
+            if let Cell::Crate = field[pos] {
+                continue
+            }
+            field[pos] = Cell::Crate;
+            if Self::is_loop(Input{field: field.clone(), guard: input.guard}) {
+                count += 1;
+            }
+            field[pos] = Cell::Empty;
         }
         count
     }
 }
-impl Solvable for PartTwo {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartTwo {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input.clone())
     }
 }
@@ -1,58 +1,36 @@
-use std::io::{BufRead, Write};
+use std::io::BufRead;
 use std::ptr::eq;
-use nom::bytes::{tag, take_while};
-use nom::combinator::{eof, map_res};
 use nom::{IResult, Parser};
-use nom::character::char;
-use nom::character::complete::{line_ending, newline};
-use nom::multi::{many0, many1, many_till, separated_list1};
-use crate::problems::common::{Readable, Solvable};
+use crate::problems::common::{Parse, Problem};
+use crate::problems::common::parse::{key_value, lines, parse_all, space_separated, unsigned};
 
+#[derive(Clone)]
 struct Equation {
     target: i64,
     numbers: Vec<i64>,
 }
-struct Input {
+#[derive(Clone)]
+pub(crate) struct Input {
     equations: Vec<Equation>,
 }
 
-fn number(input: &str) -> IResult<&str, i64> {
-    fn is_digit(c: char) -> bool {
-        c.is_digit(10)
-    }
-    map_res(
-        take_while(is_digit),
-        str::parse
-    ).parse(input)
-}
-
 fn equation(input: &str) -> IResult<&str, Equation> {
-    let (input, (target, _, numbers, _)) = (
-        number,
-        tag(": "),
-        separated_list1(char(' '), number),
-        line_ending,
+    let (input, (target, numbers)) = key_value(
+        unsigned,
+        space_separated(unsigned),
     ).parse(input)?;
     Ok((input, Equation { target, numbers }))
 }
 
-fn parse_input(input: &str) -> IResult<&str, Input> {
-    let (input, ((equations, _), _)) = (
-        many_till(equation, eof),
-        eof,
-    ).parse(input)?;
-    Ok((input, Input { equations }))
-}
-
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
     {
         let mut s = String::new();
         input.read_to_string(&mut s)?;
-        let (_, r) = parse_input(&*s).map_err(|e| anyhow::anyhow!("lol {}", e))?;
-        Ok(r)
+        let equations = parse_all(&s, lines(equation))?;
+        Ok(Input { equations })
     }
 }
 type Output = i64;
@@ -76,12 +54,12 @@ impl PartOne {
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input.clone())
     }
 }
 
@@ -108,11 +86,11 @@ impl PartTwo {
         input.equations.into_iter().filter(Self::is_valid).map(|eq| {eq.target}).sum()
     }
 }
-impl Solvable for PartTwo {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartTwo {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input.clone())
     }
 }
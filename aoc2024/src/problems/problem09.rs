@@ -1,12 +1,12 @@
-use std::io::{BufRead, Write};
+use std::io::BufRead;
 use anyhow::anyhow;
-use crate::problems::common::{Readable, Solvable};
+use crate::problems::common::{trace, Parse, Problem};
 
-struct Input {
+pub(crate) struct Input {
     compresed: Vec<u32>,
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
@@ -25,7 +25,7 @@ type Output = u64;
 
 pub(crate) struct PartOne;
 impl PartOne {
-    fn solve(&self, input: Input) -> Output {
+    fn solve(&self, input: &Input) -> Output {
         let mut uncompressed = Vec::new();
         for (i, num) in (0..).zip(&input.compresed)  {
             let elem = if i % 2 == 0 { Some(i / 2) } else { None };
@@ -45,24 +45,24 @@ impl PartOne {
                 uncompressed.pop();
             }
         }
-        // println!("{:?}", uncompressed.iter().map(|x| x.unwrap()).collect::<Vec<i32>>());
+        trace!("{:?}", uncompressed.iter().map(|x| x.unwrap()).collect::<Vec<i32>>());
         (0..).zip(uncompressed).map(|(i, x)| i * x.unwrap() as u64).sum()
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input)
     }
 }
 
 pub(crate) struct PartTwo;
 
 impl PartTwo {
-    fn solve(&self, input: Input) -> Output {
+    fn solve(&self, input: &Input) -> Output {
         let mut uncompressed = Vec::new();
         for (i, num) in (0..).zip(&input.compresed)  {
             let elem = if i % 2 == 0 { Some(i / 2) } else { None };
@@ -104,9 +104,9 @@ impl PartTwo {
                         count += 1;
                         pos -= 1;
                     }
-                    // println!("Lol {} {}", val, count);
+                    trace!("Lol {} {}", val, count);
                     if let Some(gap) = find_gap(&uncompressed, count, pos) {
-                        // println!("gap {}", gap);
+                        trace!("gap {}", gap);
                         for i in (0..count) {
                             uncompressed[gap + i] = Some(val);
                             uncompressed[pos + i + 1] = None;
@@ -115,7 +115,7 @@ impl PartTwo {
                 }
             }
         }
-        // println!("{:?}", uncompressed.iter().map(|x| x.unwrap_or(88)).collect::<Vec<_>>());
+        trace!("{:?}", uncompressed.iter().map(|x| x.unwrap_or(88)).collect::<Vec<_>>());
         (0..).zip(uncompressed).map(|(i, x)| {
             match x {
                 None => 0,
@@ -125,11 +125,11 @@ impl PartTwo {
     }
 }
 
-impl Solvable for PartTwo {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartTwo {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input)
     }
 }
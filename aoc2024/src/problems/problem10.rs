@@ -1,12 +1,12 @@
 use std::collections::{HashMap, VecDeque};
-use std::io::{BufRead, Write};
-use crate::problems::common::{Readable, Solvable};
+use std::io::BufRead;
+use crate::problems::common::{trace, Parse, Problem};
 
-struct Input {
+pub(crate) struct Input {
     field: Vec<Vec<i32>>,
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
@@ -25,7 +25,7 @@ type Output = i32;
 pub(crate) struct PartOne;
 
 impl PartOne {
-    fn solve(&self, input: Input) -> Output {
+    fn solve(&self, input: &Input) -> Output {
         let (n, m) = (input.field.len(), input.field[0].len());
         let mut sum = 0;
         for i in (0..n) {
@@ -55,7 +55,7 @@ impl PartOne {
                         }
                     }
                 }
-                // println!("New {} {} {}", i, j, nines);
+                trace!("New {} {} {}", i, j, nines);
                 sum += nines;
             }
         }
@@ -63,19 +63,19 @@ impl PartOne {
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input)
     }
 }
 
 pub(crate) struct PartTwo;
 
 impl PartTwo {
-    fn solve(&self, input: Input) -> Output {
+    fn solve(&self, input: &Input) -> Output {
         let (n, m) = (input.field.len(), input.field[0].len());
         let mut sum = 0;
         for i in (0..n) {
@@ -108,7 +108,7 @@ impl PartTwo {
                         rating[new_x as usize][new_y as usize] += rating[x as usize][y as usize];
                     }
                 }
-                // println!("New {} {} {}", i, j, nines);
+                trace!("New {} {} {}", i, j, nines);
                 sum += nines;
             }
         }
@@ -117,11 +117,11 @@ impl PartTwo {
 }
 
 
-impl Solvable for PartTwo {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartTwo {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input)
     }
 }
@@ -1,13 +1,14 @@
 use std::collections::HashMap;
-use std::io::{BufRead, Write};
+use std::io::BufRead;
 use anyhow::Error;
-use crate::problems::common::{Readable, Solvable};
+use crate::problems::common::{Parse, Problem};
 
-struct Input {
+#[derive(Clone)]
+pub(crate) struct Input {
     stones: Vec<i64>,
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
@@ -80,12 +81,12 @@ impl PartOneMut {
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
         let mut prob = PartOneMut::new(self.depth);
-        let out = prob.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+        prob.solve(input.clone())
     }
 }
@@ -1,12 +1,12 @@
-use std::{collections::VecDeque, io::{BufRead, Write}};
+use std::{collections::VecDeque, io::BufRead};
 
-use super::common::{Readable, Solvable};
+use super::common::{trace, Parse, Problem};
 
-struct Input {
+pub(crate) struct Input {
     field: Vec<Vec<char>>,
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
@@ -25,7 +25,7 @@ pub(crate) struct PartOne{}
 type Output = i32;
 
 impl PartOne {
-    fn solve(&self, input: Input) -> Output {
+    fn solve(&self, input: &Input) -> Output {
         let (n, m) = (input.field.len(), input.field[0].len());
         let mut visited = vec![vec![false; m]; n];
         let mut total = 0;
@@ -60,12 +60,12 @@ impl PartOne {
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input)
     }
 }
 
@@ -105,7 +105,7 @@ impl PartTwo {
         }
     }
 
-    fn solve(&self, input: Input) -> Output {
+    fn solve(&self, input: &Input) -> Output {
         let (n, m) = (input.field.len(), input.field[0].len());
         let mut colour = vec![vec![0; m]; n];
         let mut current_colour = 0;
@@ -133,12 +133,12 @@ impl PartTwo {
                 }
             }
         }
-        // println!("{:?}", colour);
+        trace!("{:?}", colour);
         let mut corners = vec![0; current_colour + 1];
         let mut c2 = colour;
         for i in 0..4 {
             Self::find_top_left_corners(&c2, &mut corners);
-            // println!("{:?}", corners);
+            trace!("{:?}", corners);
             c2 = Self::rotate(c2);
         }
         let mut area = vec![0; current_colour + 1];
@@ -151,11 +151,11 @@ impl PartTwo {
     }
 }
 
-impl Solvable for PartTwo {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartTwo {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input)
     }
 }
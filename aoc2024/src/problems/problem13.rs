@@ -1,69 +1,53 @@
-use std::{cmp::min, io::{BufRead, Write}};
+use std::{cmp::min, io::BufRead};
 
-use nom::{bytes::{is_not, take_while}, character::complete::newline, combinator::{eof, map_res}, multi::{many0, many_till, separated_list0}, IResult, Parser};
+use nom::{bytes::tag, character::complete::{char, line_ending}, multi::separated_list1, sequence::{preceded, separated_pair}, IResult, Parser};
 
-use super::common::{Readable, Solvable};
+use super::common::{debug, trace, Parse, Problem};
+use super::common::parse::{parse_all, unsigned, NomError};
+use super::common::geometry::{Point, Vector};
 
 
-#[derive(Debug)]
-struct Point {
-    x: i64,
-    y: i64,
-}
-
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 struct Claw {
-    a: Point, 
-    b: Point,
+    a: Vector,
+    b: Vector,
     target: Point,
 }
 
-fn number(input: &str) -> IResult<&str, i64> {
-    fn is_digit(c: char) -> bool {
-        c.is_digit(10)
-    }
-    map_res(
-        take_while(is_digit),
-        str::parse
-    ).parse(input)
-}
-
-fn point(input: &str) -> IResult<&str, Point> {
-    // let skip = many0(is_not("0123456789"));
-    let (input, (_, x, _, y, _)) = 
-         (many0(is_not("0123456789")),
-          number,
-            many0(is_not("0123456789")),
-           number, newline).parse(input)?;
-    Ok((input, Point {x, y}))
+/// `X+94, Y+34`, with `=` instead of `+` for the prize.
+fn pair<'a>(sign: char) -> impl Parser<&'a str, Output = (i64, i64), Error = NomError<'a>> {
+    separated_pair(
+        preceded((char('X'), char(sign)), unsigned),
+        tag(", "),
+        preceded((char('Y'), char(sign)), unsigned),
+    )
 }
 
 fn claw(input: &str) -> IResult<&str, Claw> {
-    let (input, (a, b, target, _)) = (point, point, point, newline).parse(input)?;
-    Ok((input, Claw {a, b, target}))
+    let (input, ((ax, ay), _, (bx, by), _, (x, y))) = (
+        preceded(tag("Button A: "), pair('+')),
+        line_ending,
+        preceded(tag("Button B: "), pair('+')),
+        line_ending,
+        preceded(tag("Prize: "), pair('=')),
+    ).parse(input)?;
+    Ok((input, Claw {a: Vector::new(ax, ay), b: Vector::new(bx, by), target: Point::new(x, y)}))
 }
 
-fn parse_input(input: &str) -> IResult<&str, Input> {
-        let (input, ((claws, _) )) = 
-            (many_till(claw, eof))
-        .parse(input)?;
-        Ok((input, Input {claws}))
-}
-
-#[derive(Debug)]
-struct Input {
+#[derive(Debug, Clone)]
+pub(crate) struct Input {
     claws: Vec<Claw>,
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
     {
         let mut s = String::new();
         input.read_to_string(&mut s)?;
-        let (_, r) = parse_input.parse(&s).map_err(|e| anyhow::anyhow!("lol {}", e))?;
-        Ok(r)
+        let claws = parse_all(&s, separated_list1((line_ending, line_ending), claw))?;
+        Ok(Input { claws })
     }
 }
 
@@ -90,25 +74,25 @@ impl PartOne {
     }
 
     fn solve(&self, input: Input) -> Output {
-        // println!("{:?}", input);
+        trace!("{:?}", input);
         input.claws.into_iter().map(|claw| {
             if Self::check(&claw) != check2(&claw) {
-                println!("BAD {:?} ", claw)
+                debug!("BAD {:?} ", claw)
             }
             if claw.a.x * claw.b.y == claw.a.y * claw.b.x {
-                println!("DEPENDENT :( {:?}", claw)
+                debug!("DEPENDENT :( {:?}", claw)
             }
             Self::check(&claw).unwrap_or(0)
         }).sum()
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input.clone())
     }
 }
 
@@ -135,28 +119,25 @@ fn check2(claw: &Claw) -> Option<i64> {
 
 impl PartTwo {
     fn solve(&self, input: Input) -> Output {
-        // println!("{:?}", input);
+        trace!("{:?}", input);
         input.claws.into_iter().map(|claw| {
             check2(&claw).unwrap_or(0)
         }).sum()
     }
 }
 
-impl Solvable for PartTwo {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let input = Input { claws: input.claws.into_iter().map(|Claw{a, b, target}| {
+impl Problem for PartTwo {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        let input = Input { claws: input.claws.iter().map(|&Claw{a, b, target}| {
             Claw {
                 a: a,
                 b: b,
-                target: Point {
-                    x: target.x + 10000000000000,
-                    y: target.y + 10000000000000,
-                },
+                target: target + Vector::new(10000000000000, 10000000000000),
             }
         }).collect()};
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+        self.solve(input)
     }
 }
@@ -1,68 +1,40 @@
-use std::{io::{BufRead, Write}, thread, time::Duration};
+use std::{io::BufRead, thread, time::Duration};
 
-use nom::{bytes::{is_not, tag, take_while}, character::complete::newline, combinator::{eof, map_res, opt}, multi::{many0, many_till}, IResult, Parser};
+use nom::{bytes::complete::tag, character::complete::char, sequence::{preceded, separated_pair}, IResult, Parser};
 
-use super::common::{Readable, Solvable};
+use super::common::{artifacts, trace, Parse, Problem};
+use super::common::parse::{lines, parse_all, signed};
+use super::common::geometry::{Point, Vector};
 
 
-#[derive(Debug)]
-struct Point {
-    x: i64,
-    y: i64,
-}
-
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Robot {
     position: Point,
-    velocity: Point,
+    velocity: Vector,
 }
 
-#[derive(Debug)]
-struct Input {
+#[derive(Debug, Clone)]
+pub(crate) struct Input {
     robots: Vec<Robot>,
 }
 
-fn number(input: &str) -> IResult<&str, i64> {
-    fn is_digit(c: char) -> bool {
-        c.is_digit(10) || c == '-'
-    }
-    map_res(
-        take_while(is_digit),
-        str::parse
-    ).parse(input)
-}
-
-fn point(input: &str) -> IResult<&str, Point> {
-    // let skip = many0(is_not("0123456789"));
-    let (input, (_, x, _, y)) = 
-         (many0(is_not("0123456789-")),
-          number,
-            many0(is_not("0123456789-")),
-           number).parse(input)?;
-    Ok((input, Point {x, y}))
-}
-
 fn robot(input: &str) -> IResult<&str, Robot> {
-    let (input, (position, velocity, _)) = (point, point, newline).parse(input)?;
-    Ok((input, Robot{position, velocity}))
-}
-
-fn parse_input(input: &str) -> IResult<&str, Input> {
-        let (input, ((robots, _) )) = 
-            (many_till(robot, eof))
-        .parse(input)?;
-        Ok((input, Input {robots}))
+    let (input, ((px, py), (vx, vy))) = (
+        preceded(tag("p="), separated_pair(signed, char(','), signed)),
+        preceded(tag(" v="), separated_pair(signed, char(','), signed)),
+    ).parse(input)?;
+    Ok((input, Robot{position: Point::new(px, py), velocity: Vector::new(vx, vy)}))
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
     {
         let mut s = String::new();
         input.read_to_string(&mut s)?;
-        let (_, r) = parse_input.parse(&s).map_err(|e| anyhow::anyhow!("lol {}", e))?;
-        Ok(r)
+        let robots = parse_all(&s, lines(robot))?;
+        Ok(Input {robots})
     }
 }
 
@@ -82,7 +54,7 @@ type Output = i64;
 
 impl PartOne {
     fn solve(&self, input: Input) -> Output {
-        // println!("{:?}", input);
+        trace!("{:?}", input);
         let wrap = |position, velocity, steps, size| {
             ((position + velocity * steps as i64) % size as i64 + size as i64) as usize % size
         };
@@ -117,8 +89,9 @@ impl PartOne {
         count[0] * count[1] * count[2] * count[3]
     }
 
-    fn solve2(&self, steps: usize, input: &Input) -> () {
-        // println!("{:?}", input);
+    /// Draws the robots after `steps` steps.
+    fn solve2(&self, steps: usize, input: &Input) -> String {
+        trace!("{:?}", input);
         let wrap = |position, velocity, steps, size| {
             ((position + velocity * steps as i64) % size as i64 + size as i64) as usize % size
         };
@@ -128,24 +101,20 @@ impl PartOne {
             let y = wrap(r.position.y, r.velocity.y, steps, self.m);
             field[x][y] = 'X';
         });
-        println!();
-        println!("STEPS {}", steps);
-        for line in field {
-            let s: String = line.iter().collect();
-            println!("{}", s);
-        }
+        field.into_iter().map(|line| line.into_iter().collect::<String>() + "\n").collect()
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        // for i in 0..1000 {
-            // self.solve2(i * 101 + 14, &input);
-        // }
-        self.solve2(6377, &input);
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        // The robots of the puzzle input draw a picture at step 6377, which
+        // only means something in the full-size room.
+        if (self.n, self.m) == (101, 103) {
+            artifacts::save("steps-6377.txt", self.solve2(6377, input));
+        }
+        self.solve(input.clone())
     }
 }
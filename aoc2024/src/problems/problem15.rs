@@ -1,55 +1,34 @@
 use core::fmt;
-use std::{io::{BufRead, Write}, str::FromStr};
+use std::str::FromStr;
 
-use super::common::{Readable, Solvable};
+use super::common::{trace, Parse, ParseError, Problem};
+use super::common::geometry::Direction;
 
 
-#[derive(Debug, Clone)]
-enum Direction {
-    Left, Up, Right, Down,
-}
-
-impl Direction {
-    fn offset(&self) -> (isize, isize) {
-        match self {
-            Direction::Left => (0, -1),
-            Direction::Up => (-1, 0),
-            Direction::Right => (0, 1),
-            Direction::Down => (1, 0),
-        }
-    }
-
-    fn is_horizontal(&self) -> bool {
-        match self {
-            Direction::Left | Direction::Right => true,
-            Direction::Up | Direction::Down => false,
-        }
-    }
-}
-
 #[derive(Debug, Clone)]
 enum Cell {
     Empty, Box, Wall
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Pos {
     x: isize,
     y: isize,
 }
 
-#[derive(Debug)]
-struct Input {
+#[derive(Debug, Clone)]
+pub(crate) struct Input {
     field: Vec<Vec<Cell>>,
     instructions: Vec<Direction>,
     start: Pos,
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: std::io::BufRead>(mut input: R) -> anyhow::Result<Self> where Self: Sized {
         let mut s = String::new();
         input.read_to_string(&mut s)?;
-        let s: Vec<&str> = s.split("\n\n").collect();
+        let text = s;
+        let s: Vec<&str> = text.split("\n\n").collect();
         let mut field = Vec::new();
         let mut start = None;
         for (x, line) in (0..).zip(s[0].lines().filter(|s| !s.is_empty())) {
@@ -63,7 +42,7 @@ impl Readable for Input {
                         start = Some(Pos {x, y});
                         Cell::Empty
                     },
-                    c => return Err(anyhow::anyhow!("<{}> is not a cell", c))
+                    _ => return Err(ParseError::at(&text, &line[y as usize..], "one of .#O@").into()),
                 };
                 f.push(c);
             }
@@ -71,14 +50,8 @@ impl Readable for Input {
         }
         let mut instructions = Vec::new();
         for line in s[1].lines() {
-            for c in line.chars() {
-                instructions.push(match c {
-                    'v' => Direction::Down,
-                    '^' => Direction::Up,
-                    '<' => Direction::Left,
-                    '>' => Direction::Right,
-                    c => return Err(anyhow::anyhow!("<{}> is not a direction", c))
-                })
+            for (i, c) in line.char_indices() {
+                instructions.push(Direction::from_arrow(c).ok_or_else(|| ParseError::at(&text, &line[i..], "one of v^<>"))?)
             }
         }
         
@@ -99,7 +72,7 @@ impl PartOne {
         let mut field = input.field.clone();
         let mut pos = input.start;
         for instruction in input.instructions {
-            let (dx, dy) = instruction.offset();
+            let (dx, dy) = instruction.delta();
             let mut boxes = 0;
             while let Cell::Box = field[(pos.x + (boxes + 1) * dx) as usize][(pos.y + (boxes + 1) * dy) as usize] {
                 boxes += 1;
@@ -125,12 +98,12 @@ impl PartOne {
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input.clone())
     }
 }
 
@@ -223,7 +196,7 @@ impl PartTwoMut {
     }
 
     fn can_shift(&self, x: usize, y: usize, dir: &Direction) -> bool {
-        let (dx, dy) = dir.offset();
+        let (dx, dy) = dir.delta();
         let (x2, y2) = ((x as isize + dx) as usize, (y as isize + dy) as usize);
         match self.field[x2][y2] {
             Cell2::Wall => false,
@@ -241,7 +214,7 @@ impl PartTwoMut {
     }
 
     fn shift(&mut self, x: usize, y: usize, dir: &Direction) -> () {
-        let (dx, dy) = dir.offset();
+        let (dx, dy) = dir.delta();
         let (x2, y2) = ((x as isize + dx) as usize, (y as isize + dy) as usize);
         match self.field[x2][y2] {
             Cell2::Wall => panic!(),
@@ -270,15 +243,15 @@ impl PartTwoMut {
 
     fn solve(&mut self) -> Output {
         for dir in self.instructions.clone() {
-            // println!("Before step {:?}:\n{:?}", dir, self);
+            trace!("Before step {:?}:\n{:?}", dir, self);
             if self.can_shift(self.pos.x as usize, self.pos.y as usize, &dir) {
                 self.shift(self.pos.x as usize, self.pos.y as usize, &dir);
-                let (dx, dy) = dir.offset();
+                let (dx, dy) = dir.delta();
                 self.pos.x += dx;
                 self.pos.y += dy;
             }
         }
-        // println!("{:?}", self.field);
+        trace!("{:?}", self.field);
         (0..).zip(self.field.clone()).map(|(x, line)| {
             (0..).zip(line).map(|(y, c)| {
                 match c {
@@ -290,11 +263,11 @@ impl PartTwoMut {
     }
 }
 
-impl Solvable for PartTwo {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input.into());
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartTwo {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input.clone().into())
     }
 }
\ No newline at end of file
@@ -1,48 +1,36 @@
-use std::io::{BufRead, Write};
-use std::str::FromStr;
+use std::io::BufRead;
 use nom::Parser;
-use nom::bytes::{tag, take_while};
+use nom::bytes::tag;
 use nom::character::complete::newline;
-use nom::combinator::map_res;
 use nom::IResult;
-use nom::multi::separated_list1;
 use nom::sequence::delimited;
-use crate::problems::common::{Readable, Solvable};
+use crate::problems::common::{trace, Parse, ParseError, Problem};
+use crate::problems::common::parse::{comma_separated, unsigned};
 
 #[derive(Debug, Clone)]
-struct Input {
+pub(crate) struct Input {
     reg_a: u64,
     reg_b: u64,
     reg_c: u64,
     program: Vec<u8>,
 }
 
-fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
-    fn is_digit(c: char) -> bool {
-        c.is_digit(10)
-    }
-    map_res(
-        take_while(is_digit),
-        str::parse
-    ).parse(input)
-}
-
 fn parse_input(input: &str) -> IResult<&str, Input> {
     fn parse_register<'a>(input: &'a str, reg: &'a str) -> IResult<&'a str, u64> {
         delimited(
             tag(format!("Register {}: ", reg).as_str()),
-            number,
+            unsigned,
             newline).parse(input)
     }
     fn parse_program(input: &str) -> IResult<&str, Vec<u8>> {
-        separated_list1(tag(","), number).parse(input)
+        comma_separated(unsigned).parse(input)
     }
 
     fn parse_reg<'a>(reg: String) -> impl Fn(&'a str) -> IResult<&'a str, u64> {
         let parse_register = move |input: &'a str| -> IResult<&'a str, u64> {
             delimited(
                 tag(format!("Register {}: ", reg).as_str()),
-                number,
+                unsigned,
                 newline).parse(input)
         };;
         parse_register
@@ -59,14 +47,15 @@ fn parse_input(input: &str) -> IResult<&str, Input> {
     Ok((input, Input { reg_a, reg_b, reg_c, program }))
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
     {
         let mut s = String::new();
         input.read_to_string(&mut s)?;
-        let (_, input) = parse_input(&s).map_err(|e| anyhow::anyhow!("lol {}", e))?;
+        // Whatever follows the program is notes, like in 02.in.
+        let (_, input) = parse_input(&s).map_err(|e| ParseError::from_nom(&s, e))?;
         Ok(input)
     }
 }
@@ -78,7 +67,7 @@ pub(crate) struct PartOne {}
 
 impl PartOne {
     fn solve(&self, input: &Input) -> Output {
-        // println!("{:?}", input);
+        trace!("{:?}", input);
         let mut reg_a = input.reg_a;
         let mut reg_b = input.reg_b;
         let mut reg_c = input.reg_c;
@@ -126,18 +115,17 @@ impl PartOne {
             };
             ip += 2;
         };
-        // println!("{:?}", out);
+        trace!("{:?}", out);
         out
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(&input);
-        let out = out.into_iter().map(|x| x.to_string()).collect::<Vec<_>>().join(",");
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input)
     }
 }
 
@@ -166,16 +154,16 @@ impl PartTwo {
                 }
             }
         }
-        // println!("{:?}", answers);
-        // println!("{:?}", answers.iter().min());
+        trace!("{:?}", answers);
+        trace!("{:?}", answers.iter().min());
         answers.into_iter().min().unwrap()
     }
 }
-impl Solvable for PartTwo {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(&input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartTwo {
+    type Input = Input;
+    type Output = Output2;
+
+    fn solve(&self, input: &Input) -> Output2 {
+        self.solve(input)
     }
 }
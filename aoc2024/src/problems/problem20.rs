@@ -1,49 +1,35 @@
-use std::collections::{HashMap, VecDeque};
-use std::io::{BufRead, Write};
-use crate::problems::common::{Readable, Solvable};
+use std::collections::HashMap;
+use std::io::BufRead;
+use aoc_core::grid::Pos;
+use crate::problems::common::{search, trace, Grid, Parse, Problem};
 
+#[derive(Clone, Copy)]
 enum Cell {
     Empty, Wall
 }
 
-struct Pos {
-    x: usize,
-    y: usize,
-}
-
-struct Input {
-    field: Vec<Vec<Cell>>,
+pub(crate) struct Input {
+    field: Grid<Cell>,
     start: Pos,
     finish: Pos,
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
     {
         let mut s = String::new();
         input.read_to_string(&mut s)?;
-        let (mut start, mut finish) = (None, None);
-        let field = s.lines().filter(|line| !line.is_empty()).enumerate().map(|(i, line)| {
-            line.chars().enumerate().map(|(j, cell)| {
-                match cell {
-                    '.' => Some(Cell::Empty),
-                    '#' => Some(Cell::Wall),
-                    'S' => {
-                        start.insert(Pos {x: i, y: j});
-                        Some(Cell::Empty)
-                    },
-                    'E' => {
-                        finish.insert(Pos {x: i, y: j});
-                        Some(Cell::Empty)
-                    }
-                    _ => None,
-                }
-            }).collect()
-        }).collect::<Option<_>>().ok_or_else(|| anyhow::anyhow!("cannot parse field"))?;
-        let start = start.ok_or_else(|| anyhow::anyhow!("no start"))?;
-        let finish = finish.ok_or_else(|| anyhow::anyhow!("no finish"))?;
+        let (field, markers) = Grid::parse_marked(&s, "one of .#SE", "SE", |cell| {
+            match cell {
+                '.' | 'S' | 'E' => Some(Cell::Empty),
+                '#' => Some(Cell::Wall),
+                _ => None,
+            }
+        })?;
+        let start = markers.one('S')?;
+        let finish = markers.one('E')?;
         Ok(Input {
             field, start, finish
         })
@@ -62,67 +48,43 @@ impl PartOne {
         Self { cheat }
     }
 
-    fn inside(input: &Input, x: isize, y: isize) -> bool {
-        let (n, m) = (input.field.len(), input.field[0].len());
-        0 <= x && x < n as isize && 0 <= y && y < m as isize
-    }
-
-    fn bfs(input: &Input, start: &Pos) -> Vec<Vec<Option<usize>>> {
-        let (n, m) = (input.field.len(), input.field[0].len());
-        let mut dist = vec![vec![None; m]; n];
-        dist[start.x][start.y] = Some(0);
-        let mut queue = VecDeque::from([(start.x, start.y)]);
-        while let Some((x, y)) = queue.pop_front() {
-            for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
-                let x2 = x as isize + dx;
-                let y2 = y as isize + dy;
-                if !Self::inside(input, x2, y2) {
-                    continue;
-                }
-                let (x2, y2) = (x2 as usize, y2 as usize);
-                if let Cell::Wall = input.field[x2][y2] {
-                    continue;
-                }
-                if dist[x2][y2].is_none() {
-                    dist[x2][y2] = dist[x][y].map(|d| d + 1);
-                    queue.push_back((x2, y2));
-                }
-            }
+    /// How far every track position is from `start`.
+    fn bfs(input: &Input, start: Pos) -> Grid<Option<usize>> {
+        let field = &input.field;
+        let paths = search::bfs([start], |&pos| {
+            field.neighbours4(pos).filter(move |&next| matches!(field[next], Cell::Empty))
+        });
+        let mut dist = Grid::new(field.rows(), field.cols(), None);
+        for (&pos, d) in paths.iter() {
+            dist[pos] = Some(d);
         }
         dist
     }
-    fn solve(&self, input: Input) -> Output {
-        let forward = Self::bfs(&input, &input.start);
-        let backward = Self::bfs(&input, &input.finish);
-        let (n, m) = (input.field.len(), input.field[0].len());
+
+    fn solve(&self, input: &Input) -> Output {
+        let forward = Self::bfs(input, input.start);
+        let backward = Self::bfs(input, input.finish);
         let mut shortcuts = HashMap::new();
-        let dist = forward[input.finish.x][input.finish.y].unwrap();
-        // println!("Finish in {}, start {:?}", dist, backward[input.start.x][input.start.y]);
-        for i in 0..n {
-            for j in 0..m {
-                for dx in (-self.cheat..=self.cheat) {
-                    for dy in (-self.cheat..=self.cheat) {
-                        if dx.abs() + dy.abs() > self.cheat {
-                            continue;
-                        }
-                        let (x2, y2) = (i as isize + dx, j as isize + dy);
-                        if !Self::inside(&input, x2, y2) {
-                            continue;
-                        }
-                        let (x2, y2) = (x2 as usize, y2 as usize);
-                        if let Some(d) = forward[i][j] {
-                            if let Some(d2) = backward[x2][y2] {
-                                let short = d + d2 + dx.abs() as usize + dy.abs() as usize;
-                                if short < dist {
-                                    *shortcuts.entry(dist - short).or_insert(0) += 1;
-                                }
-                            }
+        let dist = forward[input.finish].unwrap();
+        trace!("Finish in {}, start {:?}", dist, backward[input.start]);
+        for (pos, d) in forward.iter() {
+            let Some(d) = d else { continue };
+            for dx in -self.cheat..=self.cheat {
+                for dy in -self.cheat..=self.cheat {
+                    if dx.abs() + dy.abs() > self.cheat {
+                        continue;
+                    }
+                    let Some(pos2) = forward.offset(pos, (dx, dy)) else { continue };
+                    if let Some(d2) = backward[pos2] {
+                        let short = d + d2 + dx.unsigned_abs() + dy.unsigned_abs();
+                        if short < dist {
+                            *shortcuts.entry(dist - short).or_insert(0) += 1;
                         }
                     }
                 }
             }
         }
-        // println!("{:?}", shortcuts);
+        trace!("{:?}", shortcuts);
         shortcuts.into_iter().filter_map(|(k, v)| {
             if k >= 100 {
                 Some(v)
@@ -133,11 +95,11 @@ impl PartOne {
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input)
     }
 }
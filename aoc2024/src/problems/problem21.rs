@@ -1,32 +1,18 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Formatter;
-use std::io::{BufRead, Write};
+use std::io::BufRead;
 use anyhow::anyhow;
-use crate::problems::common::{Readable, Solvable};
+use crate::problems::common::{search, trace, Parse, Problem};
+use crate::problems::common::geometry::Direction;
 
-#[derive(Clone, PartialEq, Eq, Hash)]
-enum Direction {
-    Left, Right, Up, Down
-}
-
-impl Direction {
-    fn offset(&self) -> (isize, isize) {
-        match self {
-            Direction::Left => (0, -1),
-            Direction::Right => (0, 1),
-            Direction::Up => (-1, 0),
-            Direction::Down => (1, 0),
-        }
-    }
-
-    fn button_pos(&self) -> Vector {
-        match self {
-            Direction::Left => Vector::new(1, -2),
-            Direction::Right => Vector::new(1, 0),
-            Direction::Up => Vector::new(0, -1),
-            Direction::Down => Vector::new(1, -1),
-        }
+/// Where the button for `dir` is on the directional keypad, relative to `A`.
+fn button_pos(dir: Direction) -> Vector {
+    match dir {
+        Direction::Left => Vector::new(1, -2),
+        Direction::Right => Vector::new(1, 0),
+        Direction::Up => Vector::new(0, -1),
+        Direction::Down => Vector::new(1, -1),
     }
 }
 
@@ -45,7 +31,7 @@ struct Pos {
 
 impl Pos {
     fn shifted(&self, dir: &Direction) -> Self {
-        let (dx, dy) = dir.offset();
+        let (dx, dy) = dir.delta();
         Pos {
             x: self.x + dx,
             y: self.y + dy,
@@ -86,15 +72,7 @@ impl StateMachine {
     fn new(layers: usize) -> Self {
         let a = || Some(Action::Activate);
         let d = |digit| Some(Action::Type(digit));
-        let m = |dir| {
-            match dir {
-                '>' => Some(Action::Move(Direction::Right)),
-                '<' => Some(Action::Move(Direction::Left)),
-                'v' => Some(Action::Move(Direction::Down)),
-                '^' => Some(Action::Move(Direction::Up)),
-                _ => None
-            }
-        };
+        let m = |arrow| Direction::from_arrow(arrow).map(Action::Move);
         let mut panels: Vec<_> = (0..layers).map(|_| Panel { buttons: vec![
             vec![None, m('^'), a()],
             vec![m('<'), m('v'), m('>')],
@@ -148,7 +126,7 @@ pub struct Input {
     codes: Vec<Vec<char>>
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
@@ -189,48 +167,36 @@ impl PartOne {
     }
     pub fn solve(&self, input: &Input) -> Output {
         let state_machine = StateMachine::new(self.layers);
-        let state = state_machine.start();
-        let mut distance = HashMap::from([(state.clone(), 0)]);
-        let mut queue = VecDeque::from([(0, state)]);
-        let mut remaining_codes: HashSet<Vec<char>> = HashSet::from_iter(input.codes.clone().into_iter());
-        let mut answers = HashMap::new();
-        while let Some((dist, state)) = queue.pop_front() {
-            if remaining_codes.contains(&state.output) {
-                remaining_codes.remove(&state.output);
-                answers.insert(state.output.clone(), dist);
-            }
-            let actions = vec![
-                Action::Activate,
-                Action::Move(Direction::Left),
-                Action::Move(Direction::Right),
-                Action::Move(Direction::Up),
-                Action::Move(Direction::Down),
-            ];
-            for action in actions {
-                if let Some(new_state) = state_machine.go(&state, &action) {
-                    if self.is_dead_end(&input, &new_state) {
-                        continue
-                    }
-                    distance.entry(new_state).or_insert_with_key(|state| {
-                        queue.push_back((dist + 1, (*state).clone()));
-                        dist + 1
-                    });
-                }
-            }
-        }
-        // println!("Answers {:?}", answers);
+        let actions = [
+            Action::Activate,
+            Action::Move(Direction::Left),
+            Action::Move(Direction::Right),
+            Action::Move(Direction::Up),
+            Action::Move(Direction::Down),
+        ];
+        let paths = search::bfs([state_machine.start()], |state| {
+            actions.iter()
+                .filter_map(|action| state_machine.go(state, action))
+                .filter(|new_state| !self.is_dead_end(input, new_state))
+                .collect::<Vec<_>>()
+        });
+        let answers: HashMap<_, _> = input.codes.iter().filter_map(|code| {
+            let dist = paths.iter().filter(|(state, _)| state.output == *code).map(|(_, dist)| dist).min()?;
+            Some((code.clone(), dist as Output))
+        }).collect();
+        trace!("Answers {:?}", answers);
         answers.into_iter().map(|(mut k, v)| {
             k.pop();
             String::from_iter(k.into_iter()).parse::<i64>().unwrap() * v
         }).sum()
     }
 }
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(&input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input)
     }
 }
 
@@ -300,13 +266,13 @@ impl Vector {
             res
         }
         if self.dx == 0 {
-            vec![to_circular_path(&vec![horizontal.button_pos()])]
+            vec![to_circular_path(&vec![button_pos(horizontal)])]
         } else if self.dy == 0 {
-            vec![to_circular_path(&vec![vertical.button_pos()])]
+            vec![to_circular_path(&vec![button_pos(vertical)])]
         } else {
             vec![
-                to_circular_path(&vec![horizontal.button_pos(), vertical.button_pos()]),
-                to_circular_path(&vec![vertical.button_pos(), horizontal.button_pos()]),
+                to_circular_path(&vec![button_pos(horizontal), button_pos(vertical)]),
+                to_circular_path(&vec![button_pos(vertical), button_pos(horizontal)]),
             ]
         }
     }
@@ -327,14 +293,14 @@ impl Vector {
             res
         }
         if self.dx == 0 {
-            vec![(to_circular_path(&vec![horizontal.button_pos()]), vec![Vector::new(self.dx, self.dy)] )]
+            vec![(to_circular_path(&vec![button_pos(horizontal)]), vec![Vector::new(self.dx, self.dy)] )]
         } else if self.dy == 0 {
-            vec![(to_circular_path(&vec![vertical.button_pos()]), vec![Vector::new(self.dx, self.dy)] )]
+            vec![(to_circular_path(&vec![button_pos(vertical)]), vec![Vector::new(self.dx, self.dy)] )]
         } else {
             vec![
-                (to_circular_path(&vec![horizontal.button_pos(), vertical.button_pos()]),
+                (to_circular_path(&vec![button_pos(horizontal), button_pos(vertical)]),
                  vec![Vector::new(0, self.dy), Vector::new(self.dx, 0)]),
-                (to_circular_path(&vec![vertical.button_pos(), horizontal.button_pos()]),
+                (to_circular_path(&vec![button_pos(vertical), button_pos(horizontal)]),
                  vec![Vector::new(self.dx, 0), Vector::new(0, self.dy)]),
             ]
         }
@@ -370,16 +336,16 @@ impl PartTwoMut {
                 self.click_at(layer - 1, &v) // + v.extra_clicks()
             }).sum::<usize>()
         }).min().unwrap() + v.extra_clicks();
-        // println!("click_at({}, {:?}) = ...", layer, v);
+        trace!("click_at({}, {:?}) = ...", layer, v);
         let res2 = v.to_paths().into_iter().map(|path| {
             let x = path.iter().map(|v| {
                 self.click_at(layer - 1, &v) // + v.extra_clicks()
             }).sum::<usize>();
-            // println!("... {:?}: {}", path, x );
+            trace!("... {:?}: {}", path, x );
             x
         }).min().unwrap();
         self.cache.insert((layer, v.clone()), res);
-        // println!("=== ({}, {:?}) = {:?}", layer, v, res);
+        trace!("=== ({}, {:?}) = {:?}", layer, v, res);
         res
     }
 
@@ -401,10 +367,10 @@ impl PartTwoMut {
         if let Some(res) = self.cache2.get(&(layer, start.clone(), finish.clone())) {
             return *res;
         }
-        // println!("enter move_and_click({}, {:?}, {:?}) = ...", layer, start, finish);
+        trace!("enter move_and_click({}, {:?}, {:?}) = ...", layer, start, finish);
         let v = start.to(finish);
         let res = v.to_paths2().into_iter().filter_map(|(path_down, path_current)| {
-            // println!("... move_and_click({}, {:?}, {:?}) OPT {:?} {:?}", layer, start, finish, path_down, path_current);
+            trace!("... move_and_click({}, {:?}, {:?}) OPT {:?} {:?}", layer, start, finish, path_down, path_current);
             let mut pos_here = *start;
             for v in path_current {
                 let next = pos_here.plus(&v);
@@ -413,7 +379,7 @@ impl PartTwoMut {
                 }
                 pos_here = next;
             }
-            // println!("... ... good");
+            trace!("... ... good");
             let mut pos_down = Pos {x: 0, y: 2};
             let mut sum = 0;
             for v in path_down.iter() {
@@ -423,9 +389,9 @@ impl PartTwoMut {
             }
             Some(sum)
         }).min().unwrap() + v.extra_clicks();
-        // println!("move_and_click({}, {:?}, {:?}: {:?}) = ...", layer, start, finish, v);
+        trace!("move_and_click({}, {:?}, {:?}: {:?}) = ...", layer, start, finish, v);
         self.cache2.insert((layer, *start, *finish), res);
-        // println!("=== ({}, {:?}) = {:?}", layer, v, res);
+        trace!("=== ({}, {:?}) = {:?}", layer, v, res);
         res
     }
 
@@ -459,13 +425,13 @@ impl PartTwoMut {
     }
 
     fn solve(&mut self, input: &Input) -> Output {
-        // println!("{:?}", self.click_at(2, &Vector::new(1, -2)));
-        // println!("{:?}", self.move_and_click(3, &Pos { x: 3, y: 2 }, &Pos { x: 1, y: 0 }));
+        trace!("{:?}", self.click_at(2, &Vector::new(1, -2)));
+        trace!("{:?}", self.move_and_click(3, &Pos { x: 3, y: 2 }, &Pos { x: 1, y: 0 }));
         // return 0;
         let answers: HashMap<_, _> = input.codes.iter().map(|code| {
             (code.clone(), self.solve1(code))
         }).collect();
-        // println!("Answers {:?}", answers);
+        trace!("Answers {:?}", answers);
         answers.into_iter().map(|(mut k, v)| {
             k.pop();
             String::from_iter(k.into_iter()).parse::<i64>().unwrap() * v
@@ -473,12 +439,12 @@ impl PartTwoMut {
     }
 }
 
-impl Solvable for PartTwo {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(&input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartTwo {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input)
     }
 }
 
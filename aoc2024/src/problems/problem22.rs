@@ -1,12 +1,12 @@
 use std::collections::{HashMap, HashSet};
-use std::io::{BufRead, Write};
-use crate::problems::common::{Readable, Solvable};
+use std::io::BufRead;
+use crate::problems::common::{Parse, Problem};
 
-struct Input {
+pub(crate) struct Input {
     secrets: Vec<u64>,
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
@@ -64,12 +64,12 @@ impl PartOne {
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(&input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input)
     }
 }
 
@@ -122,17 +122,18 @@ pub(crate) struct PartTwo {
     steps: usize,
 }
 
-impl Solvable for PartTwo {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(&input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartTwo {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::problems::common::debug;
     use crate::problems::problem22::*;
 
     #[test]
@@ -146,7 +147,7 @@ mod tests {
     fn test_part2() {
         let p2 = PartTwo::new(10);
         let c = p2.changes_and_prices(123);
-        println!("{:?}", c);
+        debug!("{:?}", c);
         assert_eq!(1, 1);
     }
 }
@@ -1,12 +1,12 @@
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::io::{BufRead, Write};
+use std::io::BufRead;
 use nom::branch::alt;
 use nom::bytes::tag;
 use nom::{IResult, Parser};
-use nom::character::complete::{alphanumeric1, newline};
-use nom::multi::many1;
-use nom::sequence::terminated;
-use crate::problems::common::{Readable, Solvable};
+use nom::character::complete::{alphanumeric1, newline, one_of};
+use nom::sequence::{separated_pair, terminated};
+use crate::problems::common::{artifacts, debug, trace, Answer, Parse, Problem};
+use crate::problems::common::parse::{key_value, lines, parse_all};
 
 #[derive(Debug, Clone)]
 enum GateOp {
@@ -52,7 +52,7 @@ impl Gate {
 }
 
 #[derive(Debug, Clone)]
-struct Input {
+pub(crate) struct Input {
     gates: Vec<Gate>,
     start: HashMap<String, bool>,
 }
@@ -115,7 +115,7 @@ fn gate_op(input: &str) -> IResult<&str, GateOp> {
     )).parse(input)
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
@@ -123,27 +123,19 @@ impl Readable for Input {
         let mut s = String::new();
         input.read_to_string(&mut s)?;
         fn start(input: &str) -> IResult<&str, (String, bool)> {
-            let (input, (name, _, val, _)) = (
-                alphanumeric1,
-                tag(": "),
-                alphanumeric1,
-                newline,
-            ).parse(input)?;
-            Ok((input, (name.to_string(), if val == "0" { false } else {true})))
-        };
+            let (input, (name, val)) = key_value(alphanumeric1, one_of("01")).parse(input)?;
+            Ok((input, (name.to_string(), val == '1')))
+        }
         fn gate(input: &str) -> IResult<&str, Gate> {
             let (input, (lhs, op, rhs, target)) = (
                 terminated(alphanumeric1, tag(" ")),
                 terminated(gate_op, tag(" ")),
                 terminated(alphanumeric1, tag(" -> ")),
-                terminated(alphanumeric1, newline),
+                alphanumeric1,
             ).parse(input)?;
             Ok((input, Gate { lhs: lhs.to_string(), rhs: rhs.to_string(), op, target: target.to_string()}))
         }
-        let  (input, (start, gates)) = (
-            terminated(many1(start), newline),
-            terminated(many1(gate), newline),
-            ).parse(&*s).map_err(|e| anyhow::anyhow!("Can't parse: {}", e))?;
+        let (start, gates) = parse_all(&s, separated_pair(lines(start), newline, lines(gate)))?;
         Ok(Input {
             gates: gates,
             start: HashMap::from_iter(start.into_iter()),
@@ -192,12 +184,12 @@ impl PartOneMut {
             }
         }).collect::<Vec<_>>();
         res.sort_by(|a, b| b.0.cmp(&a.0));
-        // println!("{:?}", res);
+        trace!("{:?}", res);
         let n =
             res.iter().map(|(_, b)| {
                 if *b { "1" } else { "0" }
             }).collect::<Vec<_>>().join("").to_string();
-        // println!("{}", n);
+        trace!("{}", n);
         u64::from_str_radix(
             res.into_iter().map(|(_, b)| {
                 if b { "1" } else { "0" }
@@ -208,28 +200,32 @@ impl PartOneMut {
 
 impl PartOne {
     fn solve(&self, input: Input) -> Output {
-        // println!("{:?}", input);
+        trace!("{:?}", input);
         PartOneMut::new(input).solve()
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input.clone())
     }
 }
 
 pub(crate) struct PartTwo {}
 
+/// What's left of looking for the swaps by flipping input bits; the answer
+/// ended up being read off the circuit instead, see `SWAPS`.
+#[allow(dead_code)]
 impl PartTwo {
     fn solve(&self, input: Input) -> Output {
         PartTwoMut::new(input).solve()
     }
 }
 
+#[allow(dead_code)]
 struct PartTwoMut {
     n: usize,
     value: HashMap<String, bool>,
@@ -237,6 +233,7 @@ struct PartTwoMut {
     input: Input,
 }
 
+#[allow(dead_code)]
 impl PartTwoMut {
     fn new(input: Input) -> Self {
         let copy = input.clone();
@@ -277,14 +274,14 @@ impl PartTwoMut {
     }
 
     fn flip(&mut self, target: &String, new_val: bool) -> (bool, Vec<String>) {
-        // println!("flip: {} {:?}", target, self.value.iter().filter(|(k, v)| {**v}).collect::<Vec<_>>());
-        // println!("   xyz {:?}", self.xyz());
+        trace!("flip: {} {:?}", target, self.value.iter().filter(|(k, v)| {**v}).collect::<Vec<_>>());
+        trace!("   xyz {:?}", self.xyz());
         let mut updated = Vec::new();
         let mut queue = VecDeque::new();
         queue.push_back(target);
         self.value.insert(target.to_string(), new_val);
         while let Some(node) = queue.pop_front() {
-            // println!("GO FROM {} {:?}", node, self.links.get(node));
+            trace!("GO FROM {} {:?}", node, self.links.get(node));
             for gate in self.links.get(node).unwrap() {
                 let cur_val = *self.value.get(&gate.target).unwrap();
                 let lhs = *self.value.get(&gate.lhs).unwrap();
@@ -306,7 +303,7 @@ impl PartTwoMut {
             let x = format!("x{:0>2}", i);
             let (ok, updated) = self.flip(&x, true);
             if !ok {
-                // println!("{}: {:?} {:?}", i, updated, self.xyz());
+                trace!("{}: {:?} {:?}", i, updated, self.xyz());
                 count += 1;
             }
             self.flip(&x, false);
@@ -323,11 +320,11 @@ impl PartTwoMut {
                 let score = p2.score();
                 if score <= best {
                     best = score;
-                    println!("Check {} - {}: {}", change, node.target, score);
+                    debug!("Check {} - {}: {}", change, node.target, score);
                 }
             }
         }
-        println!("SCORE {}", self.score());
+        debug!("SCORE {}", self.score());
     }
 
     fn solve(&mut self) -> Output {
@@ -335,57 +332,57 @@ impl PartTwoMut {
             let x = format!("x{:0>2}", i);
             let (ok, updated) = self.flip(&x, true);
             if !ok {
-                println!("{}: {:?} {:?}", i, updated, self.xyz());
+                debug!("{}: {:?} {:?}", i, updated, self.xyz());
             } else {
                 let y = format!("y{:0>2}", i);
                 let (ok, updated) = self.flip(&y, true);
                 if !ok {
-                    println!("Step 2 {}: {:?} {:?}", i, updated, self.xyz());
+                    debug!("Step 2 {}: {:?} {:?}", i, updated, self.xyz());
                 }
                 self.flip(&y, false);
             }
             self.flip(&x, false);
         }
         // self.find_best_score("mwh".to_string());
-        println!("SCORE {}", self.score());
+        debug!("SCORE {}", self.score());
         0
     }
 }
 
-impl Solvable for PartTwo {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let input = input.swap("fhc".to_string(), "z06".to_string()).unwrap();
-        let input = input.swap("qhj".to_string(), "z11".to_string()).unwrap();
-        let input = input.swap("hqk".to_string(), "z35".to_string()).unwrap();
-        let input = input.swap("mwh".to_string(), "ggt".to_string()).unwrap();
-
-        for g in input.gates.iter() {
-            writeln!(output, "{} [shape={}]", g.target, g.op.shape());
-            writeln!(output, "{} -> {};", g.lhs, g.target);
-            writeln!(output, "{} -> {};", g.rhs, g.target);
-        }
-
-        write!(output, "{{ rank = same; ");
-        for i in 0..44 {
-            write!(output, "x{:0>2}; ", i);
-        }
-        writeln!(output, "}};");
-        write!(output, "{{ rank = same; ");
-        for i in 0..44 {
-            write!(output, "y{:0>2}; ", i);
-        }
-        writeln!(output, "}};");
-        write!(output, "{{ rank = same; ");
-        for i in 0..45 {
-            write!(output, "z{:0>2}; ", i);
+/// Output pairs that are swapped in the puzzle input, found by hand by looking
+/// at the circuit drawn by `dot`. Part 2 answers with these as they are.
+const SWAPS: [(&str, &str); 4] = [("fhc", "z06"), ("qhj", "z11"), ("hqk", "z35"), ("mwh", "ggt")];
+
+/// The circuit in Graphviz format, inputs and outputs ranked by bit.
+fn dot(input: &Input) -> String {
+    let mut output = String::new();
+    for g in input.gates.iter() {
+        output += &format!("{} [shape={}]\n", g.target, g.op.shape());
+        output += &format!("{} -> {};\n", g.lhs, g.target);
+        output += &format!("{} -> {};\n", g.rhs, g.target);
+    }
+    for (wire, bits) in [('x', 44), ('y', 44), ('z', 45)] {
+        output += "{ rank = same; ";
+        for i in 0..bits {
+            output += &format!("{}{:0>2}; ", wire, i);
         }
-        writeln!(output, "}};");
-
+        output += "};\n";
+    }
+    output
+}
 
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
+impl Problem for PartTwo {
+    type Input = Input;
+    type Output = Answer;
 
-        Ok(())
+    fn solve(&self, input: &Input) -> Answer {
+        let mut input = input.clone();
+        for (a, b) in SWAPS {
+            input = input.swap(a.to_string(), b.to_string()).unwrap();
+        }
+        artifacts::save("circuit.dot", dot(&input));
+        let mut wires: Vec<_> = SWAPS.iter().flat_map(|&(a, b)| [a, b]).collect();
+        wires.sort();
+        wires.into()
     }
 }
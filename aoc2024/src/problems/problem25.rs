@@ -1,12 +1,13 @@
-use std::io::{BufRead, Write};
+use std::io::BufRead;
 use std::iter::zip;
-use crate::problems::common::{Readable, Solvable};
+use crate::problems::common::{trace, Parse, Problem};
 
-struct Input {
+#[derive(Clone)]
+pub(crate) struct Input {
     schemes: Vec<Vec<String>>
 }
 
-impl Readable for Input {
+impl Parse for Input {
     fn parse_from<R: BufRead>(mut input: R) -> anyhow::Result<Self>
     where
         Self: Sized
@@ -46,7 +47,7 @@ impl PartOne {
                 keys.push(Self::tumblers(&block));
             }
         }
-        // println!("{:?} {:?}", locks, keys);
+        trace!("{:?} {:?}", locks, keys);
         locks.iter().map(|lock| {
             keys.iter().map(|key| {
                 if zip(lock, key).all(|(l, k)| l + k < 6) {
@@ -59,11 +60,11 @@ impl PartOne {
     }
 }
 
-impl Solvable for PartOne {
-    fn solve<R: BufRead, W: Write>(&self, input: R, mut output: W) -> anyhow::Result<()> {
-        let input = Input::parse_from(input)?;
-        let out = self.solve(input);
-        writeln!(output, "{}", out)?;
-        Ok(())
+impl Problem for PartOne {
+    type Input = Input;
+    type Output = Output;
+
+    fn solve(&self, input: &Input) -> Output {
+        self.solve(input.clone())
     }
 }
@@ -0,0 +1,81 @@
+use aoc_core::Registry;
+
+use crate::problems::*;
+
+pub fn register_all(registry: &mut Registry) {
+    registry.add(0, 1, |_| problem00::PartOne{});
+
+    registry.add(1, 1, |_| problem01::PartOne{});
+    registry.add(1, 2, |_| problem01::PartTwo{});
+
+    registry.add(2, 1, |_| problem02::PartOne{});
+    registry.add(2, 2, |_| problem02::PartTwo{});
+
+    registry.add(3, 1, |_| problem03::PartOne{});
+    registry.add(3, 2, |_| problem03::PartTwo{});
+
+    registry.add(4, 1, |_| problem04::PartOne{});
+    registry.add(4, 2, |_| problem04::PartTwo{});
+
+    registry.add(5, 1, |_| problem05::PartOne{});
+    registry.add(5, 2, |_| problem05::PartTwo{});
+
+    registry.add(6, 1, |_| problem06::PartOne{});
+    registry.add(6, 2, |_| problem06::PartTwo{}).slow();
+
+    registry.add(7, 1, |_| problem07::PartOne{});
+    registry.add(7, 2, |_| problem07::PartTwo{});
+
+    registry.add(8, 1, |_| problem08::PartOne{});
+    registry.add(8, 2, |_| problem08::PartTwo{});
+
+    registry.add(9, 1, |_| problem09::PartOne{});
+    registry.add(9, 2, |_| problem09::PartTwo{}).slow();
+
+    registry.add(10, 1, |_| problem10::PartOne{});
+    registry.add(10, 2, |_| problem10::PartTwo{});
+
+    registry.add(11, 0, |_| problem11::PartOne::new(6));
+    registry.add(11, 1, |_| problem11::PartOne::new(25));
+    registry.add(11, 2, |_| problem11::PartOne::new(75));
+
+    registry.add(12, 1, |_| problem12::PartOne{});
+    registry.add(12, 2, |_| problem12::PartTwo{});
+
+    registry.add(13, 1, |_| problem13::PartOne{});
+    registry.add(13, 2, |_| problem13::PartTwo{});
+
+    registry.add_params(14, 1, |p| Ok(problem14::PartOne::new(p.get("width")?, p.get("height")?)));
+
+    registry.add(15, 1, |_| problem15::PartOne{});
+    registry.add(15, 2, |_| problem15::PartTwo{});
+
+    registry.add(16, 1, |_| problem16::PartOne{});
+    registry.add(16, 2, |_| problem16::PartTwo{});
+
+    registry.add(17, 1, |_| problem17::PartOne{});
+    registry.add(17, 2, |_| problem17::PartTwo{});
+
+    registry.add_params(18, 1, |p| Ok(problem18::PartOne::new(p.get("width")?, p.get("height")?, p.get("fallen")?)));
+    registry.add_params(18, 2, |p| Ok(problem18::PartTwo::new(p.get("width")?, p.get("height")?)));
+
+    registry.add(19, 1, |_| problem19::PartOne{});
+    registry.add(19, 2, |_| problem19::PartTwo{});
+
+    registry.add(20, 1, |_| problem20::PartOne::new(2));
+    registry.add(20, 2, |_| problem20::PartOne::new(20));
+
+    registry.add_params(21, 1, |p| Ok(problem21::PartOne::new(p.get_or("layers", 2)?)));
+    registry.add_params(21, 2, |p| Ok(problem21::PartTwo::new(p.get_or("layers", 25)?)));
+
+    registry.add(22, 1, |_| problem22::PartOne::new(2000));
+    registry.add(22, 2, |_| problem22::PartTwo::new(2000)).slow();
+
+    registry.add(23, 1, |_| problem23::PartOne {});
+    registry.add(23, 2, |_| problem23::PartTwo {}).slow();
+
+    registry.add(24, 1, |_| problem24::PartOne {});
+    registry.add(24, 2, |_| problem24::PartTwo {});
+
+    registry.add(25, 1, |_| problem25::PartOne {});
+}
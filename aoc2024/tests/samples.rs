@@ -0,0 +1,8 @@
+use std::process::ExitCode;
+use aoc_core::Registry;
+
+fn main() -> ExitCode {
+    let mut registry = Registry::default();
+    aoc2024::register_all(&mut registry);
+    aoc_core::testing::main(registry)
+}
@@ -0,0 +1,3 @@
+mod problems;
+
+pub use problems::register_all;
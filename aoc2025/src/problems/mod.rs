@@ -12,3 +12,19 @@ pub(crate) mod problem09;
 pub(crate) mod problem10;
 pub(crate) mod problem11;
 pub(crate) mod problem12;
+
+pub fn register_all(registry: &mut aoc_core::Registry) {
+    problem00::register(registry);
+    problem01::register(registry);
+    problem02::register(registry);
+    problem03::register(registry);
+    problem04::register(registry);
+    problem05::register(registry);
+    problem06::register(registry);
+    problem07::register(registry);
+    problem08::register(registry);
+    problem09::register(registry);
+    problem10::register(registry);
+    problem11::register(registry);
+    problem12::register(registry);
+}
@@ -1,23 +1,32 @@
 use std::io::BufRead;
-use crate::problems::common::Problem;
+use crate::problems::common::{Parse, Problem};
+use aoc_core::Registry;
 
-pub struct Problem00 {}
-
-impl Problem for Problem00 {
-    type Input = (i32, i32);
-    type Output = i32;
+pub struct Input(i32, i32);
 
-    fn parse_from<R: BufRead>(&self, mut buf: R) -> anyhow::Result<(i32, i32)> {
+impl Parse for Input {
+    fn parse_from<R: BufRead>(mut buf: R) -> anyhow::Result<Self> {
         let mut s = String::new();
         buf.read_to_string(&mut s)?;
         let v =
             s.split_whitespace().map(|w| {
                 w.parse::<i32>().map_err(anyhow::Error::new)
             }).collect::<Result<Vec<_>, _>>()?;
-        Ok((v[0], v[1]))
+        Ok(Input(v[0], v[1]))
     }
+}
+
+pub struct Problem00 {}
+
+impl Problem for Problem00 {
+    type Input = Input;
+    type Output = i32;
 
-    fn solve(&self, input: Self::Input) -> Self::Output {
+    fn solve(&self, input: &Self::Input) -> Self::Output {
         input.0 + input.1
     }
 }
+
+pub(crate) fn register(registry: &mut Registry) {
+    registry.add(0, 1, |_| Problem00 {});
+}
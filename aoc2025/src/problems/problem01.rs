@@ -1,6 +1,7 @@
 use std::io::BufRead;
 use anyhow::{anyhow};
-use crate::problems::common::Problem;
+use crate::problems::common::{Parse, Problem};
+use aoc_core::Registry;
 
 enum Direction {
     Left, Right
@@ -29,11 +30,8 @@ impl Problem01 {
     }
 }
 
-impl Problem for Problem01 {
-    type Input = Input;
-    type Output = i32;
-
-    fn parse_from<R: BufRead>(&self, buf: R) -> anyhow::Result<Self::Input> {
+impl Parse for Input {
+    fn parse_from<R: BufRead>(buf: R) -> anyhow::Result<Self> {
         let rotations = buf.lines().map(|line| {
             let line = line?;
             let (lhs, rhs) = line.split_at(1);
@@ -49,11 +47,16 @@ impl Problem for Problem01 {
         }).collect::<anyhow::Result<_>>()?;
         Ok(Input { rotations })
     }
+}
+
+impl Problem for Problem01 {
+    type Input = Input;
+    type Output = i32;
 
-    fn solve(&self, input: Self::Input) -> Self::Output {
+    fn solve(&self, input: &Self::Input) -> Self::Output {
         let mut count = 0;
         let mut pos = 50;
-        for rotation in input.rotations {
+        for rotation in &input.rotations {
             if !self.is_part2 {
                 match rotation.dir {
                     Direction::Left => {
@@ -84,4 +87,9 @@ impl Problem for Problem01 {
         }
         count
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn register(registry: &mut Registry) {
+    registry.add(1, 1, |_| Problem01::new_part1());
+    registry.add(1, 2, |_| Problem01::new_part2());
+}
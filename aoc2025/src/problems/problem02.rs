@@ -1,14 +1,14 @@
+use std::collections::BTreeSet;
 use std::io::BufRead;
-use anyhow::Context;
-use crate::problems::common::Problem;
-
-struct Range {
-    left: u64,
-    right: u64,
-}
+use nom::character::complete::char;
+use nom::sequence::separated_pair;
+use nom::Parser;
+use crate::problems::common::{IntervalSet, Parse, Problem};
+use crate::problems::common::parse::{comma_separated, parse_all, unsigned};
+use aoc_core::Registry;
 
 pub struct Input {
-    ranges: Vec<Range>
+    ids: IntervalSet<u64>,
 }
 
 pub struct Problem02 {
@@ -25,56 +25,55 @@ impl Problem02 {
     }
 }
 
+impl Parse for Input {
+    fn parse_from<R: BufRead>(mut buf: R) -> anyhow::Result<Self> {
+        let mut s = String::new();
+        buf.read_to_string(&mut s)?;
+        let range = separated_pair(unsigned, char('-'), unsigned).map(|(left, right)| left..=right);
+        let ranges = parse_all(&s, comma_separated(range))?;
+        Ok(Input { ids: ranges.into_iter().collect() })
+    }
+}
+
 impl Problem for Problem02 {
     type Input = Input;
     type Output = u64;
 
-    fn parse_from<R: BufRead>(&self, buf: R) -> anyhow::Result<Self::Input> {
-        let mut ranges = Vec::new();
-        for line in buf.lines() {
-            for range in line?.split(',') {
-                let (lhs, rhs) = range.split_once('-').with_context(|| "Can't parse range")?;
-                ranges.push(Range {
-                    left: lhs.parse()?,
-                    right: rhs.parse()?,
-                })
-            }
-        }
-        Ok(Input { ranges })
+    fn solve(&self, input: &Self::Input) -> Self::Output {
+        let Some(max) = input.ids.max() else {
+            return 0
+        };
+        repeated_digits(max, self.is_part1).into_iter().filter(|&id| input.ids.contains(id)).sum()
     }
+}
 
-    fn solve(&self, input: Self::Input) -> Self::Output {
-        fn is_invalid(num: u64) -> bool {
-            let s = num.to_string();
-            let (lhs, rhs) = s.split_at(s.len() / 2);
-            lhs == rhs
-        }
-        fn is_invalid2(num: u64) -> bool {
-            let s = num.to_string();
-            for len in 1..s.len() {
-                if s.len() % len != 0 {
-                    continue
-                }
-                let bad = (0..(s.len() / len)).map(|i| {
-                    &s[(i * len)..((i+1) * len)] == &s[0..len]
-                }).all(|x| x);
-                if bad {
-                    return true
-                }
+/// Every number up to `max` whose digits are one block repeated: exactly
+/// twice if `twice`, at least twice otherwise.
+fn repeated_digits(max: u64, twice: bool) -> BTreeSet<u64> {
+    let mut found = BTreeSet::new();
+    let digits = max.to_string().len() as u32;
+    for len in 2..=digits {
+        for block in (1..len).filter(|block| len % block == 0) {
+            let repeats = len / block;
+            if twice && repeats != 2 {
+                continue
             }
-            false
-        }
-        let mut sum = 0;
-        for range in input.ranges {
-            for x in range.left..=range.right {
-                if self.is_part1 && is_invalid(x) {
-                    sum += x;
-                }
-                if (!self.is_part1) && is_invalid2(x) {
-                    sum += x;
-                }
+            // 1, then `block - 1` zeros, repeated: 10101 for 3 blocks of 2.
+            let Some(unit) = (0..repeats).try_fold(0u64, |unit, _| unit.checked_mul(10u64.pow(block))?.checked_add(1)) else {
+                continue
+            };
+            for first in 10u64.pow(block - 1)..10u64.pow(block) {
+                match first.checked_mul(unit) {
+                    Some(id) if id <= max => found.insert(id),
+                    _ => break,
+                };
             }
         }
-        sum
     }
-}
\ No newline at end of file
+    found
+}
+
+pub(crate) fn register(registry: &mut Registry) {
+    registry.add(2, 1, |_| Problem02::new());
+    registry.add(2, 2, |_| Problem02::new_part2());
+}
@@ -1,6 +1,7 @@
 use std::cmp::max;
 use std::io::BufRead;
-use crate::problems::common::Problem;
+use crate::problems::common::{Parse, Problem};
+use aoc_core::Registry;
 
 pub struct Input {
     batteries: Vec<Vec<u8>>
@@ -16,11 +17,8 @@ impl Problem03 {
     pub(crate) fn new_part2() -> Self { Self { is_part1: false } }
 }
 
-impl Problem for Problem03 {
-    type Input = Input;
-    type Output = u64;
-
-    fn parse_from<R: BufRead>(&self, buf: R) -> anyhow::Result<Self::Input> {
+impl Parse for Input {
+    fn parse_from<R: BufRead>(buf: R) -> anyhow::Result<Self> {
         let mut batteries = Vec::new();
         for line in buf.lines() {
             let battery = line?.chars().map(|c| c as u8 - b'0').collect::<Vec<u8>>();
@@ -28,8 +26,13 @@ impl Problem for Problem03 {
         }
         Ok(Input { batteries })
     }
+}
+
+impl Problem for Problem03 {
+    type Input = Input;
+    type Output = u64;
 
-    fn solve(&self, input: Self::Input) -> Self::Output {
+    fn solve(&self, input: &Self::Input) -> Self::Output {
         fn solve1(input: &Vec<u8>) -> u64 {
             let mut max: u64 = 0;
             for i in 0..input.len() {
@@ -65,4 +68,9 @@ impl Problem for Problem03 {
             input.batteries.iter().map(solve1_2).sum()
         }
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn register(registry: &mut Registry) {
+    registry.add(3, 1, |_| Problem03::new());
+    registry.add(3, 2, |_| Problem03::new_part2());
+}
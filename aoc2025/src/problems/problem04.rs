@@ -1,6 +1,7 @@
 use std::cmp::PartialEq;
 use std::io::BufRead;
-use crate::problems::common::Problem;
+use crate::problems::common::{Grid, Parse, Problem};
+use aoc_core::Registry;
 
 #[derive(Eq, PartialEq, Clone)]
 enum Cell {
@@ -8,7 +9,7 @@ enum Cell {
 }
 
 pub struct Input {
-    field: Vec<Vec<Cell>>
+    field: Grid<Cell>
 }
 
 pub(crate) struct Problem04 {
@@ -21,63 +22,47 @@ impl Problem04 {
     pub fn new_part2() -> Self { Self { is_part1: false } }
 }
 
+impl Parse for Input {
+    fn parse_from<R: BufRead>(mut buf: R) -> anyhow::Result<Self> {
+        let mut s = String::new();
+        buf.read_to_string(&mut s)?;
+        let field = Grid::parse(&s, "one of @.", |c| {
+            match c {
+                '@' => Some(Cell::PaperRoll),
+                '.' => Some(Cell::Empty),
+                _ => None,
+            }
+        })?;
+        Ok(Input { field })
+    }
+}
+
 impl Problem for Problem04 {
     type Input = Input;
     type Output = u64;
 
-    fn parse_from<R: BufRead>(&self, buf: R) -> anyhow::Result<Self::Input> {
-        let mut field = Vec::new();
-        for line in buf.lines() {
-            let line = line?;
-            field.push(line.chars().map(|c| {
-                match c {
-                    '@' => Cell::PaperRoll,
-                    _ => Cell::Empty,
-                }
-            }).collect())
-        }
-        Ok(Input { field })
-    }
-
-    fn solve(&self, input: Self::Input) -> Self::Output {
-        let rows = input.field.len();
-        let cols = input.field[0].len();
-        let can_remove = |field: &Vec<Vec<Cell>>, x: usize, y: usize| -> bool {
-            let mut count = 0;
-            for dx in (-1 as isize)..=1 {
-                for dy in (-1 as isize)..=1 {
-                    let x = x as isize + dx;
-                    let y = y as isize + dy;
-                    if 0 <= x && x < rows as isize && 0 <= y && y < cols as isize {
-                        if let Cell::PaperRoll = field[x as usize][y as usize] {
-                            count += 1;
-                        }
-                    }
-                }
-            }
-            count <= 4 && field[x][y] == Cell::PaperRoll
+    fn solve(&self, input: &Self::Input) -> Self::Output {
+        let can_remove = |field: &Grid<Cell>, pos| -> bool {
+            let count = field.neighbours8(pos).filter(|&pos| field[pos] == Cell::PaperRoll).count();
+            count < 4 && field[pos] == Cell::PaperRoll
         };
 
         let mut answer = 0;
         if self.is_part1 {
-            for i in 0..rows {
-                for j in 0..cols {
-                    if can_remove(&input.field, i, j) {
-                        answer += 1;
-                    }
+            for pos in input.field.positions() {
+                if can_remove(&input.field, pos) {
+                    answer += 1;
                 }
             }
         } else {
             let mut field = input.field.clone();
             loop {
                 let mut updated = false;
-                for i in 0..rows {
-                    for j in 0..cols {
-                        if can_remove(&field, i, j) {
-                            answer += 1;
-                            field[i][j] = Cell::Empty;
-                            updated = true;
-                        }
+                for pos in field.positions() {
+                    if can_remove(&field, pos) {
+                        answer += 1;
+                        field[pos] = Cell::Empty;
+                        updated = true;
                     }
                 }
                 if !updated {
@@ -87,4 +72,9 @@ impl Problem for Problem04 {
         }
         answer
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn register(registry: &mut Registry) {
+    registry.add(4, 1, |_| Problem04::new());
+    registry.add(4, 2, |_| Problem04::new_part2());
+}
@@ -1,15 +1,13 @@
-use std::cmp::{max, min};
 use std::io::BufRead;
-use anyhow::Context;
-use crate::problems::common::Problem;
+use nom::character::complete::{char, line_ending};
+use nom::sequence::separated_pair;
+use nom::Parser;
+use crate::problems::common::{IntervalSet, Parse, Problem};
+use crate::problems::common::parse::{lines, parse_all, unsigned};
+use aoc_core::Registry;
 
-#[derive(Clone)]
-struct Range {
-    from: u64,
-    to: u64,
-}
 pub struct Input {
-    ranges: Vec<Range>,
+    fresh: IntervalSet<u64>,
     ids: Vec<u64>,
 }
 
@@ -22,63 +20,30 @@ impl Problem05 {
     pub fn new_part2() -> Self { Self { is_part1: false } }
 }
 
-impl Problem for Problem05 {
-    type Input = Input;
-    type Output = usize;
-
-    fn parse_from<R: BufRead>(&self, mut buf: R) -> anyhow::Result<Self::Input> {
+impl Parse for Input {
+    fn parse_from<R: BufRead>(mut buf: R) -> anyhow::Result<Self> {
         let mut s = String::new();
         buf.read_to_string(&mut s)?;
-        let (ranges_str, ids_str) = s.split_once("\n\n").with_context(|| "can't split")?;
-
-        let mut ranges = Vec::new();
-        for range in ranges_str.lines() {
-            let (from, to) = range.split_once('-').with_context(|| "can't split range")?;
-            ranges.push(Range { from: from.parse()?, to: to.parse()? });
-        }
-
-        let ids = ids_str.lines().map(|line| {
-            line.parse::<u64>().with_context(|| format!("can't parse id {}", line))
-        }).collect::<anyhow::Result<_>>()?;
-
-        Ok(Input { ranges, ids })
+        let range = separated_pair(unsigned, char('-'), unsigned).map(|(from, to)| from..=to);
+        let (ranges, ids) = parse_all(&s, separated_pair(lines(range), line_ending, lines(unsigned)))?;
+        Ok(Input { fresh: ranges.into_iter().collect(), ids })
     }
+}
+
+impl Problem for Problem05 {
+    type Input = Input;
+    type Output = usize;
 
-    fn solve(&self, input: Self::Input) -> Self::Output {
+    fn solve(&self, input: &Self::Input) -> Self::Output {
         if self.is_part1 {
-            input.ids.iter().filter(|&id| {
-                input.ranges.iter().any(|range| {
-                    range.from <= *id && *id <= range.to
-                })
-            }).count()
+            input.ids.iter().filter(|&&id| input.fresh.contains(id)).count()
         } else {
-            let mut ranges = input.ranges.clone();
-            fn merge(range1: &Range, range2: &Range) -> Option<Range> {
-                if range1.to < range2.from || range2.to < range1.from {
-                    None
-                } else {
-                    Some(Range {
-                        from: min(range1.from, range2.from),
-                        to: max(range1.to, range2.to),
-                    })
-                }
-            }
-            'outer: loop {
-                for i in 0..ranges.len() {
-                    for j in i+1..ranges.len() {
-                        if let Some(new_range) = merge(&ranges[i], &ranges[j]) {
-                            ranges.remove(j);
-                            ranges.remove(i);
-                            ranges.push(new_range);
-                            continue 'outer;
-                        }
-                    }
-                }
-                break;
-            }
-            ranges.iter().map(|range| {
-                (range.to - range.from + 1) as usize
-            }).sum()
+            usize::try_from(input.fresh.len()).unwrap()
         }
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn register(registry: &mut Registry) {
+    registry.add(5, 1, |_| Problem05::new());
+    registry.add(5, 2, |_| Problem05::new_part2());
+}
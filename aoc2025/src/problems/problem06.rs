@@ -1,7 +1,8 @@
 use std::io::{BufRead};
 use anyhow::Context;
 use regex::Regex;
-use crate::problems::common::Problem;
+use crate::problems::common::{Parse, Problem};
+use aoc_core::Registry;
 
 #[derive(Clone)]
 enum Operation {
@@ -17,17 +18,13 @@ pub struct Input {
     equations: Vec<Equation>,
 }
 
-pub struct Problem06 {}
-
-impl Problem06 {
-    pub fn new() -> Self { Self {} }
+/// The same worksheet read right to left, one number per column.
+pub struct InputPart2 {
+    equations: Vec<Equation>,
 }
 
-impl Problem for Problem06 {
-    type Input = Input;
-    type Output = u64;
-
-    fn parse_from<R: BufRead>(&self, buf: R) -> anyhow::Result<Self::Input> {
+impl Parse for Input {
+    fn parse_from<R: BufRead>(buf: R) -> anyhow::Result<Self> {
         let mut rows = Vec::new();
         let mut ops = Vec::new();
         let nums_re = Regex::new(r"^[0-9 ]+$").unwrap();
@@ -57,28 +54,10 @@ impl Problem for Problem06 {
         }).collect();
         Ok(Input { equations })
     }
-
-    fn solve(&self, input: Self::Input) -> Self::Output {
-        input.equations.iter().map(|equation| {
-            match equation.operation {
-                Operation::Plus => equation.operands.iter().sum::<u64>(),
-                Operation::Multiply => equation.operands.iter().product::<u64>(),
-            }
-        }).sum()
-    }
-}
-
-pub(crate) struct Problem06Part2 {}
-
-impl Problem06Part2 {
-    pub fn new() -> Self { Self {} }
 }
 
-impl Problem for Problem06Part2 {
-    type Input = Input;
-    type Output = u64;
-
-    fn parse_from<R: BufRead>(&self, buf: R) -> anyhow::Result<Self::Input> {
+impl Parse for InputPart2 {
+    fn parse_from<R: BufRead>(buf: R) -> anyhow::Result<Self> {
         let mut lines = buf.lines().map(|line| line.with_context(|| "what")).collect::<anyhow::Result<Vec<_>>>()?;
         let max_len = lines.iter().map(|line| line.len()).max().with_context(|| "no lines")?;
         let mut ops = lines.pop().with_context(|| "no last line")?;
@@ -120,10 +99,41 @@ impl Problem for Problem06Part2 {
             }
             equations.push(Equation { operation: op, operands });
         }
-        Ok(Input { equations })
+        Ok(InputPart2 { equations })
+    }
+}
+
+pub struct Problem06 {}
+
+impl Problem06 {
+    pub fn new() -> Self { Self {} }
+}
+
+impl Problem for Problem06 {
+    type Input = Input;
+    type Output = u64;
+
+    fn solve(&self, input: &Self::Input) -> Self::Output {
+        input.equations.iter().map(|equation| {
+            match equation.operation {
+                Operation::Plus => equation.operands.iter().sum::<u64>(),
+                Operation::Multiply => equation.operands.iter().product::<u64>(),
+            }
+        }).sum()
     }
+}
+
+pub(crate) struct Problem06Part2 {}
 
-    fn solve(&self, input: Self::Input) -> Self::Output {
+impl Problem06Part2 {
+    pub fn new() -> Self { Self {} }
+}
+
+impl Problem for Problem06Part2 {
+    type Input = InputPart2;
+    type Output = u64;
+
+    fn solve(&self, input: &Self::Input) -> Self::Output {
         input.equations.iter().map(|equation| {
             match equation.operation {
                 Operation::Plus => equation.operands.iter().sum::<u64>(),
@@ -131,4 +141,9 @@ impl Problem for Problem06Part2 {
             }
         }).sum()
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn register(registry: &mut Registry) {
+    registry.add(6, 1, |_| Problem06::new());
+    registry.add(6, 2, |_| Problem06Part2::new());
+}
@@ -1,14 +1,14 @@
 use std::collections::HashSet;
 use std::io::BufRead;
-use anyhow::Context;
-use crate::problems::common::Problem;
+use crate::problems::common::{Grid, Parse, Problem};
+use aoc_core::Registry;
 
 enum Cell {
     Empty, Splitter
 }
 
 pub struct Input {
-    field: Vec<Vec<Cell>>,
+    field: Grid<Cell>,
     start_column: usize,
 }
 
@@ -20,10 +20,11 @@ impl Problem07 {
     pub(crate) fn new() -> Self { Self { is_part1: true } }
     pub(crate) fn new_part2() -> Self { Self { is_part1: false } }
 
-    fn solve_part1(&self, input: Input) -> <Problem07 as Problem>::Output {
+    fn solve_part1(&self, input: &Input) -> <Problem07 as Problem>::Output {
         let mut beams = HashSet::from([input.start_column]);
         let mut result = 0;
-        for line in input.field {
+        for row in 0..input.field.rows() {
+            let line = input.field.row(row);
             let mut new_beams = HashSet::new();
             for beam in beams {
                 match line[beam] {
@@ -42,14 +43,13 @@ impl Problem07 {
         result
     }
 
-    fn solve_part2(&self, input: Input) -> <Problem07 as Problem>::Output {
-        let rows = input.field.len();
-        let cols = input.field[0].len();
+    fn solve_part2(&self, input: &Input) -> <Problem07 as Problem>::Output {
+        let (rows, cols) = (input.field.rows(), input.field.cols());
         let mut dp = vec![vec![0; cols]; rows];
         dp.push(vec![1; cols]);
         for i in (0..rows).rev() {
             for j in 0..cols {
-                match input.field[i][j] {
+                match input.field[(i, j)] {
                     Cell::Empty => {
                         dp[i][j] = dp[i + 1][j];
                     }
@@ -63,38 +63,39 @@ impl Problem07 {
     }
 }
 
-impl Problem for Problem07 {
-    type Input = Input;
-    type Output = usize;
-
-    fn parse_from<R: BufRead>(&self, buf: R) -> anyhow::Result<Self::Input> {
-        let mut start_column = None;
-        let lines: Vec<String> = buf.lines().map(|line| line.with_context(|| "wtf")).collect::<anyhow::Result<Vec<_>>>()?;
-        let field = lines.iter().map(|line| {
-            line.chars().map(|c| {
-                match c {
-                    '^' => Cell::Splitter,
-                    _ => Cell::Empty,
-                }
-            }).collect()
-        }).collect();
-        for (i, c) in lines[0].chars().enumerate() {
-            if c == 'S' {
-                start_column = Some(i);
+impl Parse for Input {
+    fn parse_from<R: BufRead>(mut buf: R) -> anyhow::Result<Self> {
+        let mut s = String::new();
+        buf.read_to_string(&mut s)?;
+        let (field, markers) = Grid::parse_marked(&s, "one of S^.", "S", |c| {
+            match c {
+                '^' => Some(Cell::Splitter),
+                'S' | '.' => Some(Cell::Empty),
+                _ => None,
             }
-        }
-        let start_column = start_column.with_context(|| "no start")?;
+        })?;
+        let (_, start_column) = markers.one('S')?;
         Ok(Input {
             field,
             start_column,
         })
     }
+}
 
-    fn solve(&self, input: Self::Input) -> Self::Output {
+impl Problem for Problem07 {
+    type Input = Input;
+    type Output = usize;
+
+    fn solve(&self, input: &Self::Input) -> Self::Output {
         if self.is_part1 {
             self.solve_part1(input)
         } else {
             self.solve_part2(input)
         }
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn register(registry: &mut Registry) {
+    registry.add(7, 1, |_| Problem07::new());
+    registry.add(7, 2, |_| Problem07::new_part2());
+}
@@ -1,20 +1,16 @@
 use std::collections::HashMap;
 use std::io::BufRead;
 use anyhow::Context;
-use crate::problems::common::Problem;
-
-struct Point {
-    coords: [i64; 3],
-}
-
-impl Point {
-    fn dist2(&self, other: &Point) -> i64 {
-        self.coords.iter().zip(other.coords.iter()).map(|(a, b)| (a - b) * (a - b)).sum()
-    }
-}
+use nom::character::complete::char;
+use nom::sequence::preceded;
+use nom::Parser;
+use crate::problems::common::{Parse, Problem};
+use crate::problems::common::geometry::Point3;
+use crate::problems::common::parse::{lines, parse_all, signed};
+use aoc_core::Registry;
 
 pub struct Input {
-    points: Vec<Point>,
+    points: Vec<Point3>,
 }
 
 pub(crate) struct Problem08 {
@@ -27,12 +23,12 @@ impl Problem08 {
 
     pub fn new_part2() -> Self { Self { is_part1: false, steps: 0} }
 
-    pub fn solve_part1(&self, input: Input) -> <Problem08 as Problem>::Output {
+    pub fn solve_part1(&self, input: &Input) -> <Problem08 as Problem>::Output {
         let mut dsu = DSU::new(input.points.len());
         let mut distances = Vec::new();
         for i in 0..input.points.len() {
             for j in i + 1..input.points.len() {
-                distances.push((input.points[i].dist2(&input.points[j]), i, j))
+                distances.push((input.points[i].dist2(input.points[j]), i, j))
             }
         }
         distances.sort_by(|a, b| a.0.cmp(&b.0));
@@ -51,12 +47,12 @@ impl Problem08 {
         }).product()
     }
 
-    pub fn solve_part2(&self, input: Input) -> <Problem08 as Problem>::Output {
+    pub fn solve_part2(&self, input: &Input) -> <Problem08 as Problem>::Output {
         let mut dsu = DSU::new(input.points.len());
         let mut distances = Vec::new();
         for i in 0..input.points.len() {
             for j in i + 1..input.points.len() {
-                distances.push((input.points[i].dist2(&input.points[j]), i, j))
+                distances.push((input.points[i].dist2(input.points[j]), i, j))
             }
         }
         distances.sort_by(|a, b| a.0.cmp(&b.0));
@@ -66,7 +62,7 @@ impl Problem08 {
                 dsu.merge(p1, p2).unwrap();
                 merges += 1;
                 if merges == input.points.len() - 1 {
-                    return (input.points[p1].coords[0] * input.points[p2].coords[0]) as u64
+                    return (input.points[p1].x * input.points[p2].x) as u64
                 }
             }
         }
@@ -105,29 +101,30 @@ impl DSU {
     }
 }
 
+impl Parse for Input {
+    fn parse_from<R: BufRead>(mut buf: R) -> anyhow::Result<Self> {
+        let mut s = String::new();
+        buf.read_to_string(&mut s)?;
+        let point = (signed, preceded(char(','), signed), preceded(char(','), signed)).map(|(x, y, z)| Point3::new(x, y, z));
+        let points = parse_all(&s, lines(point))?;
+        Ok(Input { points })
+    }
+}
+
 impl Problem for Problem08 {
     type Input = Input;
     type Output = u64;
 
-    fn parse_from<R: BufRead>(&self, buf: R) -> anyhow::Result<Self::Input> {
-        let mut points = Vec::new();
-        for line in buf.lines() {
-            let line = line?;
-            let p = line.split(',').map(|s| {
-                s.parse::<i64>().with_context(|| format!("could not parse number {}", s))
-            }).collect::<anyhow::Result<Vec<_>>>()?;
-
-            let p: anyhow::Result<_> = p.as_slice().try_into().with_context(|| "lol");
-            points.push(Point { coords: p? });
-        }
-        Ok(Input { points })
-    }
-
-    fn solve(&self, input: Self::Input) -> Self::Output {
+    fn solve(&self, input: &Self::Input) -> Self::Output {
         if self.is_part1 {
             self.solve_part1(input)
         } else {
             self.solve_part2(input)
         }
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn register(registry: &mut Registry) {
+    registry.add_params(8, 1, |p| Ok(Problem08::new(p.get("steps")?)));
+    registry.add(8, 2, |_| Problem08::new_part2());
+}
@@ -1,14 +1,13 @@
 use std::cmp::{max, min, PartialEq};
-use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::collections::{BTreeSet, HashMap};
 use std::io::BufRead;
-use anyhow::Context;
-use crate::problems::common::Problem;
-
-#[derive(Clone)]
-struct Point {
-    x: i64,
-    y: i64,
-}
+use nom::character::complete::char;
+use nom::sequence::separated_pair;
+use nom::Parser;
+use crate::problems::common::{search, Parse, Problem};
+use crate::problems::common::geometry::Point;
+use crate::problems::common::parse::{lines, parse_all, signed};
+use aoc_core::Registry;
 
 pub struct Input {
     points: Vec<Point>,
@@ -61,20 +60,16 @@ impl Polygon {
                 }
             }
         }
-        let mut queue = VecDeque::new();
         // assume 0, 0 is outside (lol)
-        queue.push_back((0, 0));
-        while let Some((x, y)) = queue.pop_front() {
-            for (dx, dy) in [(-1isize, 0), (1, 0), (0, -1isize), (0, 1)] {
-                let (x2, y2) = (x + dx, y + dy);
-                if !(0 <= x2 && x2 < field[0].len() as isize && 0 <= y2 && y2 < field.len() as isize) {
-                    continue
-                }
-                if let Cell::Unknown = field[y2 as usize][x2 as usize] {
-                    field[y2 as usize][x2 as usize] = Cell::Outside;
-                    queue.push_back((x2, y2));
-                }
-            }
+        let (rows, cols) = (field.len(), field[0].len());
+        let outside = search::bfs([Point::ORIGIN], |p| {
+            p.neighbours4().filter(|next| {
+                next.to_pos().is_some_and(|(row, col)| row < rows && col < cols && field[row][col] == Cell::Unknown)
+            }).collect::<Vec<_>>()
+        });
+        for (p, _) in outside.iter() {
+            let (row, col) = p.to_pos().unwrap();
+            field[row][col] = Cell::Outside;
         }
 
         Self {
@@ -87,24 +82,21 @@ impl Polygon {
 }
 
 
+impl Parse for Input {
+    fn parse_from<R: BufRead>(mut buf: R) -> anyhow::Result<Self> {
+        let mut s = String::new();
+        buf.read_to_string(&mut s)?;
+        let point = separated_pair(signed, char(','), signed).map(|(x, y)| Point::new(x, y));
+        let points = parse_all(&s, lines(point))?;
+        Ok(Input { points })
+    }
+}
+
 impl Problem for Problem09 {
     type Input = Input;
     type Output = i64;
 
-    fn parse_from<R: BufRead>(&self, buf: R) -> anyhow::Result<Self::Input> {
-        let mut points = Vec::new();
-        for line in buf.lines() {
-            let line = line?;
-            let (x, y) = line.split_once(",").with_context(|| "lol")?;
-            points.push(Point {
-                x: x.parse()?,
-                y: y.parse()?,
-            })
-        }
-        Ok(Input { points })
-    }
-
-    fn solve(&self, input: Self::Input) -> Self::Output {
+    fn solve(&self, input: &Self::Input) -> Self::Output {
         if self.is_part1 {
             input.points.iter().map(|p1| {
                 input.points.iter().map(|p2| {
@@ -114,7 +106,7 @@ impl Problem for Problem09 {
                 }).max().unwrap()
             }).max().unwrap()
         } else {
-            let polygon = Polygon::new(&input);
+            let polygon = Polygon::new(input);
             input.points.iter().map(|p1| {
                 input.points.iter().filter_map(|p2| {
                     let (x1, x2) = (polygon.x_map[&p1.x], polygon.x_map[&p2.x]);
@@ -135,4 +127,9 @@ impl Problem for Problem09 {
             }).max().unwrap_or(0)
         }
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn register(registry: &mut Registry) {
+    registry.add(9, 1, |_| Problem09::new());
+    registry.add(9, 2, |_| Problem09::new_part2());
+}
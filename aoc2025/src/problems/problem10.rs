@@ -1,8 +1,14 @@
 use std::cmp::min;
 use std::collections::HashMap;
 use std::io::BufRead;
-use anyhow::Context;
-use crate::problems::common::Problem;
+use nom::character::complete::{char, one_of, space1};
+use nom::multi::many1;
+use nom::combinator::cut;
+use nom::sequence::{delimited, preceded, terminated};
+use nom::Parser;
+use crate::problems::common::{artifacts, debug, trace, Parse, Problem};
+use crate::problems::common::parse::{comma_separated, lines, parse_all, unsigned};
+use aoc_core::Registry;
 
 #[derive(Debug)]
 pub struct Machine {
@@ -21,14 +27,14 @@ pub struct MachineState<'a> {
 
 impl MachineState<'_> {
     fn go(&mut self) -> Option<usize> {
-        // println!("GO {:?} {:?}", self.remaining_joltage, self.used_buttons);
+        trace!("GO {:?} {:?}", self.remaining_joltage, self.used_buttons);
         if self.current + (*self.remaining_joltage.iter().max().unwrap() as usize) >= self.best {
             return None;
         }
         if self.remaining_joltage.iter().all(|j| *j == 0) {
-            // println!("YOOOOOOO");
+            trace!("YOOOOOOO");
             if self.current < self.best {
-                println!("... found {} < {}", self.current, self.best);
+                debug!("... found {} < {}", self.current, self.best);
             }
             self.best = min(self.best, self.current);
             return Some(0)
@@ -45,7 +51,7 @@ impl MachineState<'_> {
                 *rarity.entry(*light).or_insert(0) += 1
             }
         }
-        // println!("rarity: {:?} {:?} {:?}", rarity, self.remaining_joltage, self.used_buttons);
+        trace!("rarity: {:?} {:?} {:?}", rarity, self.remaining_joltage, self.used_buttons);
         let (rarest_light, rarest_count) = rarity.into_iter().min_by_key(|(_light, cnt)| {
             *cnt
         }).unwrap();
@@ -68,7 +74,7 @@ impl MachineState<'_> {
             }).min().unwrap();
             (0..=mn).collect()
         };
-        // println!("rarity2: {:?} button {:?} ({:?}), trying {:?}", rarest_light, button_with_rarest_light, self.machine.buttons[button_with_rarest_light], clicks_cnt);
+        trace!("rarity2: {:?} button {:?} ({:?}), trying {:?}", rarest_light, button_with_rarest_light, self.machine.buttons[button_with_rarest_light], clicks_cnt);
         self.used_buttons[button_with_rarest_light] = true;
         for clicks in clicks_cnt {
             for light in self.machine.buttons[button_with_rarest_light].iter() {
@@ -150,7 +156,7 @@ impl Machine {
                 let sum = clicks.into_iter().map(|c| {
                     c.as_u64().unwrap()
                 }).sum();
-                println!("YAY {sum}");
+                artifacts::trace("z3.txt", format!("  {} presses", sum));
                 best = sum;
             } else {
                 break
@@ -173,59 +179,39 @@ impl Problem10 {
     pub fn new_part2() -> Self { Self { is_part1: false } }
 }
 
+impl Parse for Input {
+    fn parse_from<R: BufRead>(mut buf: R) -> anyhow::Result<Self> {
+        let mut s = String::new();
+        buf.read_to_string(&mut s)?;
+        let lights = delimited(char('['), many1(one_of(".#").map(|c| c == '#')), char(']'));
+        // Past a `(`, a malformed button is an error rather than the end of the list.
+        let button = preceded(char('('), cut(terminated(comma_separated(unsigned), char(')'))));
+        let joltage = delimited(char('{'), comma_separated(unsigned), char('}'));
+        let machine = (terminated(lights, space1), many1(terminated(button, space1)), joltage)
+            .map(|(lights, buttons, joltage)| Machine { lights, buttons, joltage });
+        let machines = parse_all(&s, lines(machine))?;
+        Ok(Input { machines })
+    }
+}
+
 impl Problem for Problem10 {
     type Input = Input;
     type Output = usize;
 
-    fn parse_from<R: BufRead>(&self, buf: R) -> anyhow::Result<Self::Input> {
-        let mut machines = Vec::new();
-        for line in buf.lines() {
-            let line = line?;
-            let mut lights = None;
-            let mut buttons = Vec::new();
-            let mut joltage = None;
-            for part in line.split_whitespace() {
-                match part.chars().next() {
-                    Some('[') => {
-                        let part = part.replace("[", "").replace("]", "");
-                        lights = Some(part.chars().map(|c| c == '#').collect::<Vec<_>>());
-                    },
-                    Some('(') => {
-                        let part = part.replace("(", "").replace(")", "");
-                        let button = part.split(",").into_iter().map(|x| {
-                                x.parse().with_context(|| format!("Can't parse {}", x))
-                            }).collect::<anyhow::Result<Vec<_>>>()?;
-                        buttons.push(button);
-                    },
-                    Some('{') => {
-                        let part = part.replace("{", "").replace("}", "");
-                        joltage = Some(part.split(",").into_iter().map(|x| {
-                            x.parse().with_context(|| format!("Can't parse {}", x))
-                        }).collect::<anyhow::Result<Vec<_>>>()?)
-                    },
-                    _ => anyhow::bail!("don't know what to do with {}", part),
-                }
-            }
-            machines.push(Machine {
-                lights: lights.with_context(|| "no []")?,
-                buttons,
-                joltage: joltage.with_context(|| "no {}")?,
-            })
-        }
-        Ok(Input {
-            machines,
-        })
-    }
-
-    fn solve(&self, input: Self::Input) -> Self::Output {
-        input.machines.into_iter().map(|machine| {
+    fn solve(&self, input: &Self::Input) -> Self::Output {
+        input.machines.iter().map(|machine| {
             if self.is_part1 {
                 machine.min_presses()
             } else {
-                println!("Solved another one {:?}", machine);
+                artifacts::trace("z3.txt", format!("{:?}", machine));
                 // machine.min_presses_2()
                 machine.min_presses_2_z3()
             }
         }).sum()
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn register(registry: &mut Registry) {
+    registry.add(10, 1, |_| Problem10::new());
+    registry.add(10, 2, |_| Problem10::new_part2());
+}
@@ -1,7 +1,9 @@
 use std::collections::{HashMap, HashSet};
 use std::io::BufRead;
-use anyhow::Context;
-use crate::problems::common::Problem;
+use nom::character::complete::alphanumeric1;
+use crate::problems::common::{Parse, Problem};
+use crate::problems::common::parse::{key_value, lines, parse_all, space_separated};
+use aoc_core::Registry;
 
 type Vertex = String;
 
@@ -46,21 +48,23 @@ impl Problem11 {
     pub fn new_part2() -> Self { Self { is_part1: false } }
 }
 
-impl Problem for Problem11 {
-    type Input = Input;
-    type Output = usize;
-
-    fn parse_from<R: BufRead>(&self, buf: R) -> anyhow::Result<Self::Input> {
+impl Parse for Input {
+    fn parse_from<R: BufRead>(mut buf: R) -> anyhow::Result<Self> {
+        let mut s = String::new();
+        buf.read_to_string(&mut s)?;
         let mut edges = HashMap::new();
-        for line in buf.lines() {
-            let line = line?;
-            let (from, to) = line.split_once(": ").with_context(|| "edges")?;
-            edges.entry(from.to_string()).or_insert_with(Vec::new).extend(to.split_whitespace().map(String::from));
+        for (from, to) in parse_all(&s, lines(key_value(alphanumeric1, space_separated(alphanumeric1))))? {
+            edges.entry(from.to_string()).or_insert_with(Vec::new).extend(to.into_iter().map(String::from));
         }
         Ok(Input{edges})
     }
+}
+
+impl Problem for Problem11 {
+    type Input = Input;
+    type Output = usize;
 
-    fn solve(&self, input: Self::Input) -> Self::Output {
+    fn solve(&self, input: &Self::Input) -> Self::Output {
         if self.is_part1 {
             input.paths(&String::from("you"), &String::from("out"))
         } else {
@@ -72,4 +76,9 @@ impl Problem for Problem11 {
                 input.paths(svr, dac) * input.paths(dac, fft) * input.paths(fft, out)
         }
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn register(registry: &mut Registry) {
+    registry.add(11, 1, |_| Problem11::new());
+    registry.add(11, 2, |_| Problem11::new_part2());
+}
@@ -1,8 +1,11 @@
 use std::array;
 use std::collections::HashSet;
 use std::io::BufRead;
-use anyhow::Context;
-use crate::problems::common::Problem;
+use nom::character::complete::char;
+use nom::sequence::{separated_pair, terminated};
+use crate::problems::common::{debug, trace, Grid, Parse, ParseError, Problem};
+use crate::problems::common::parse::{blocks, key_value, lines, parse_all, space_separated, unsigned};
+use aoc_core::Registry;
 
 type Fig = [[bool; 3]; 3];
 
@@ -99,7 +102,7 @@ impl<'a> Bruteforcer<'a> {
         }
         shape.iter().enumerate().all(|(i, row)| {
             row.iter().enumerate().all(|(j, cell)| {
-                // println!("!!!! {} {} {} {}", pos[0] + i, pos[1] + j, self.field.len(), self.field[0].len());
+                trace!("!!!! {} {} {} {}", pos[0] + i, pos[1] + j, self.field.len(), self.field[0].len());
                 !cell || !self.field[pos[0] + i][pos[1] + j]
             })
         })
@@ -177,44 +180,35 @@ impl Problem12 {
     pub fn new() -> Self { Self {} }
 }
 
-impl Problem for Problem12 {
-    type Input = Input;
-    type Output = usize;
-
-    fn parse_from<R: BufRead>(&self, mut buf: R) -> anyhow::Result<Self::Input> {
+impl Parse for Input {
+    fn parse_from<R: BufRead>(mut buf: R) -> anyhow::Result<Self> {
         let mut s = String::new();
         buf.read_to_string(&mut s)?;
         let mut presents = Vec::new();
         let mut regions = Vec::new();
-        for block in s.split("\n\n") {
-            if block.contains("x") {
-                for block in block.lines() {
-                    let (dimensions, counts) = block.split_once(": ").with_context(|| "dim: cnt")?;
-                    let (d1, d2) = dimensions.split_once("x").with_context(|| "dim")?;
-                    let (d1, d2) = (d1.parse()?, d2.parse()?);
-                    let present_counts = counts.split_whitespace().map(|c| {
-                        c.parse::<usize>().with_context(|| "count")
-                    }).collect::<anyhow::Result<_>>()?;
+        for block in blocks(&s) {
+            if !block.lines().next().is_some_and(|header| header.ends_with(':')) {
+                let region = key_value(separated_pair(unsigned, char('x'), unsigned), space_separated(unsigned));
+                for ((d1, d2), present_counts) in parse_all(block, lines(region)).map_err(|e| e.within(&s, block))? {
                     regions.push(Region {
                         dimensions: [d1, d2],
                         present_counts,
                     })
                 }
             } else {
-                let mut lines: Vec<_> = block.lines().collect();
-                let id = lines.remove(0);
-                let id = id.trim_end_matches(":").parse()?;
-                let lines: Vec<Vec<_>> = lines.into_iter().map(|line| {
-                    line.chars().collect()
-                }).collect();
-                let shape = array::from_fn(|i| {
-                    array::from_fn(|j| {
-                        lines[i][j] == '#'
-                    })
-                });
+                let (header, shape) = block.split_once('\n').ok_or_else(|| ParseError::at(&s, block, "a present"))?;
+                let id = parse_all(header, terminated(unsigned, char(':'))).map_err(|e| e.within(&s, header))?;
+                let shape = Grid::parse(shape, "one of #.", |c| match c {
+                    '#' => Some(true),
+                    '.' => Some(false),
+                    _ => None,
+                }).map_err(|e| e.within(&s, shape))?;
+                if (shape.rows(), shape.cols()) != (3, 3) {
+                    return Err(ParseError::at(&s, block, "a 3x3 present").into());
+                }
                 presents.push(Present {
                     id,
-                    shape,
+                    shape: array::from_fn(|i| array::from_fn(|j| shape[(i, j)])),
                 })
             }
         }
@@ -223,21 +217,30 @@ impl Problem for Problem12 {
             regions,
         })
     }
+}
 
-    fn solve(&self, input: Self::Input) -> Self::Output {
+impl Problem for Problem12 {
+    type Input = Input;
+    type Output = usize;
+
+    fn solve(&self, input: &Self::Input) -> Self::Output {
         for (i, present) in input.presents.iter().enumerate() {
-            println!("Present {} has {} variants", i, present.variants().len())
+            debug!("Present {} has {} variants", i, present.variants().len())
         }
 
         let mut count = 0;
         for (i, region) in input.regions.iter().enumerate() {
-            println!("Solving region {} ({} x {})", i, region.dimensions[0], region.dimensions[1]);
-            let bruteforcer = Bruteforcer::new(&input, region);
+            debug!("Solving region {} ({} x {})", i, region.dimensions[0], region.dimensions[1]);
+            let bruteforcer = Bruteforcer::new(input, region);
             if bruteforcer.solve() {
-                println!("it fits");
+                debug!("it fits");
                 count += 1;
             }
         }
         count
     }
-}
\ No newline at end of file
+}
+
+pub(crate) fn register(registry: &mut Registry) {
+    registry.add(12, 1, |_| Problem12::new());
+}